@@ -1,5 +1,6 @@
-use std::fs::File;
-use std::io::prelude::*;
+use std::fmt::Display;
+
+use utils::solution::{Result, Solution};
 
 fn instruction_weight(ch: char) -> i32 {
     match ch {
@@ -28,16 +29,23 @@ fn find_basement_position(input: &String) -> Option<usize> {
     None
 }
 
-fn main() {
-    let mut file = File::open("input.txt").expect("Failed to read input");
-    let mut input = String::new();
-    file.read_to_string(&mut input).unwrap();
-    let result = calc_floor(&input);
-    println!("result = {}", result);
-
-    if let Some(basement_position) = find_basement_position(&input) {
-        println!("Basement position = {}", basement_position);
-    } else {
-        println!("Basement not found");
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        calc_floor(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        match find_basement_position(input) {
+            Some(basement_position) => basement_position.to_string(),
+            None => String::from("Basement not found"),
+        }
     }
 }
@@ -1,7 +1,8 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use std::fmt::Display;
 
-struct GiftBox {
+use utils::solution::{Result, Solution};
+
+pub struct GiftBox {
     dimensions: [usize; 3],
 }
 
@@ -65,22 +66,26 @@ impl GiftBox {
     }
 }
 
-fn main() {
-    let file = File::open("input.txt").expect("Failed to read input");
-    let buffer = BufReader::new(file);
+pub struct Day02;
 
-    let mut total_papaer = 0;
-    let mut total_ribbon = 0;
+impl Solution for Day02 {
+    type Input = Vec<GiftBox>;
 
-    for line in buffer.lines() {
-        if let Ok(line) = line {
-            if let Some(gift) = GiftBox::parse(&line) {
-                total_papaer += gift.paper_size();
-                total_ribbon += gift.ribbon_length();
-            }
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut gifts = Vec::new();
+        for line in input.lines() {
+            let gift = GiftBox::parse(&line.to_string())
+                .ok_or_else(|| format!("Invalid gift box: {}", line))?;
+            gifts.push(gift);
         }
+        Ok(gifts)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        input.iter().map(GiftBox::paper_size).sum::<usize>()
     }
 
-    println!("total paper surface: {}", total_papaer);
-    println!("total ribbon length: {}", total_ribbon);
+    fn part2(input: &Self::Input) -> impl Display {
+        input.iter().map(GiftBox::ribbon_length).sum::<usize>()
+    }
 }
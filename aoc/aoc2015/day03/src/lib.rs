@@ -0,0 +1,34 @@
+use std::fmt::Display;
+
+use utils::solution::{Result, Solution};
+
+use crate::board::Board;
+
+mod board;
+
+fn visit_count(input: &str, workers: &[&str]) -> usize {
+    let mut board = Board::new();
+    for worker in workers {
+        board.add_worker(worker);
+    }
+    board.perform_transitions(input.to_owned());
+    board.visit_count()
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.trim().to_owned())
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        visit_count(input, &["santa"])
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        visit_count(input, &["santa", "robot"])
+    }
+}
@@ -1,6 +1,8 @@
+use std::fmt::Display;
 use std::{ops::Range, thread};
 
 use md5;
+use utils::solution::{Result, Solution};
 
 fn calculate_hash(secret: &str, number: usize) -> String {
     let hash = md5::compute(format!("{}{}", secret, number).as_bytes());
@@ -21,7 +23,7 @@ fn process_range(secret: &str, predict: &str, range: Range<usize>) -> Option<(us
     None
 }
 
-fn mine_block(secret: &'static str, predict: &'static str) -> (usize, String) {
+fn mine_block(secret: &str, predict: &str) -> (usize, String) {
     let cpu_count = num_cpus::get();
     let step = 100_000;
 
@@ -33,7 +35,9 @@ fn mine_block(secret: &'static str, predict: &'static str) -> (usize, String) {
             let range = counter..counter + step;
 
             counter += step;
-            let handle = thread::spawn(move || process_range(secret, predict, range));
+            let secret = secret.to_owned();
+            let predict = predict.to_owned();
+            let handle = thread::spawn(move || process_range(&secret, &predict, range));
             handles.push(handle);
         }
         for handle in handles {
@@ -44,11 +48,20 @@ fn mine_block(secret: &'static str, predict: &'static str) -> (usize, String) {
     }
 }
 
-fn main() {
-    let secret = "iwrupvqb";
-    let part1 = mine_block(secret, "00000");
-    println!("Part1: {} - {}", part1.0, part1.1);
+pub struct Day04;
 
-    let part2 = mine_block(secret, "000000");
-    println!("Part2: {} - {}", part2.0, part2.1);
+impl Solution for Day04 {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.trim().to_owned())
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        mine_block(input, "00000").0
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        mine_block(input, "000000").0
+    }
 }
@@ -1,5 +1,6 @@
-use std::io::prelude::*;
-use std::{fs::File, io::BufReader};
+use std::fmt::Display;
+
+use utils::solution::{Result, Solution};
 
 fn vowel_count(word: &str) -> usize {
     let mut count = 0;
@@ -73,24 +74,22 @@ fn is_nice_2(word: &str) -> bool {
     find_repeated_pair(word).is_some() && find_repeating_pattern(word).is_some()
 }
 
-fn main() {
-    let file = File::open("input.txt").expect("Failed to open input.txt");
-    let buffer = BufReader::new(file);
+pub struct Day05;
 
-    let mut nice_count_1 = 0;
-    let mut nice_count_2 = 0;
+impl Solution for Day05 {
+    type Input = Vec<String>;
 
-    for line in buffer.lines() {
-        let line = line.expect("Failed to read line");
-        if is_nice(&line) {
-            nice_count_1 += 1;
-        }
-        if is_nice_2(&line) {
-            nice_count_2 += 1;
-        }
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(str::to_owned).collect())
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        input.iter().filter(|line| is_nice(line)).count()
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        input.iter().filter(|line| is_nice_2(line)).count()
     }
-    println!("nice count 1: {}", nice_count_1);
-    println!("nice count 2: {}", nice_count_2);
 }
 
 #[cfg(test)]
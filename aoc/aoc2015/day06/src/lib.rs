@@ -0,0 +1,40 @@
+mod lights;
+
+use std::fmt::Display;
+
+use utils::solution::{Result, Solution};
+
+use crate::lights::{extract_command, Command, Lights, Mode};
+
+fn apply_commands(commands: &[Command], mode: Mode) -> Lights {
+    let mut lights = Lights::new(1000, mode);
+    for command in commands {
+        lights
+            .apply_command(command.clone())
+            .expect("Failed to apply command");
+    }
+    lights
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Vec<Command>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut commands = Vec::new();
+        for line in input.lines() {
+            let command = extract_command(line).map_err(|e| format!("{}: {}", e, line))?;
+            commands.push(command);
+        }
+        Ok(commands)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        apply_commands(input, Mode::Switch).lit_count()
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        apply_commands(input, Mode::Brightness).total_brightness()
+    }
+}
@@ -1,12 +1,14 @@
 use std::{fmt::Display, ops::Add};
 
+use utils::grid::Grid;
+
 #[derive(Debug, Clone)]
 pub struct Coordinate {
     x: usize,
     y: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Command {
     TurnOn(Coordinate, Coordinate),
     TurnOff(Coordinate, Coordinate),
@@ -22,28 +24,44 @@ impl Display for GridCommandError {
     }
 }
 
+/// How commands change a light: by switching it on and off, or by
+/// adjusting its brightness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Switch,
+    Brightness,
+}
+
 #[derive(Debug)]
-pub struct Grid {
-    cells: Vec<Vec<usize>>,
+pub struct Lights {
+    mode: Mode,
+    cells: Grid<usize>,
 }
 
-impl Grid {
-    pub fn new(size: usize) -> Self {
+impl Lights {
+    pub fn new(size: usize, mode: Mode) -> Self {
         Self {
-            cells: vec![vec![0; size]; size],
+            mode,
+            cells: Grid::new(size, size, 0),
         }
     }
 
+    fn update_cells<F>(&mut self, start: Coordinate, end: Coordinate, update: F)
+    where
+        F: Fn(usize) -> usize,
+    {
+        self.cells
+            .update_rect(start.x..end.x + 1, start.y..end.y + 1, |cell| {
+                *cell = update(*cell)
+            });
+    }
+
     fn adjust_coordinates(
         &self,
         c1: &Coordinate,
         c2: &Coordinate,
     ) -> Result<(Coordinate, Coordinate), GridCommandError> {
-        if c1.x < self.cells.len()
-            && c2.x < self.cells.len()
-            && c1.y < self.cells[0].len()
-            && c2.y < self.cells[0].len()
-        {
+        if self.cells.contains((c1.x, c1.y)) && self.cells.contains((c2.x, c2.y)) {
             let x_start = if c1.x < c2.x { c1.x } else { c2.x };
             let x_end = if c1.x > c2.x { c1.x } else { c2.x };
             let y_start = if c1.y < c2.y { c1.y } else { c2.y };
@@ -64,28 +82,23 @@ impl Grid {
         match command {
             Command::TurnOn(c1, c2) => {
                 let (start, end) = self.adjust_coordinates(&c1, &c2)?;
-                for x in start.x..=end.x {
-                    for y in start.y..=end.y {
-                        self.cells[x][y] += 1;
-                    }
+                match self.mode {
+                    Mode::Switch => self.update_cells(start, end, |_| 1),
+                    Mode::Brightness => self.update_cells(start, end, |v| v + 1),
                 }
             }
             Command::TurnOff(c1, c2) => {
                 let (start, end) = self.adjust_coordinates(&c1, &c2)?;
-                for x in start.x..=end.x {
-                    for y in start.y..=end.y {
-                        if self.cells[x][y] > 0 {
-                            self.cells[x][y] -= 1;
-                        }
-                    }
+                match self.mode {
+                    Mode::Switch => self.update_cells(start, end, |_| 0),
+                    Mode::Brightness => self.update_cells(start, end, |v| v.saturating_sub(1)),
                 }
             }
             Command::Toggle(c1, c2) => {
                 let (start, end) = self.adjust_coordinates(&c1, &c2)?;
-                for x in start.x..=end.x {
-                    for y in start.y..=end.y {
-                        self.cells[x][y] += 2;
-                    }
+                match self.mode {
+                    Mode::Switch => self.update_cells(start, end, |v| 1 - v),
+                    Mode::Brightness => self.update_cells(start, end, |v| v + 2),
                 }
             }
         }
@@ -93,17 +106,11 @@ impl Grid {
     }
 
     pub fn lit_count(&self) -> usize {
-        self.cells.iter().fold(0, |acc, row| {
-            acc + row
-                .iter()
-                .fold(0, |acc, cell| if *cell > 0 { acc + 1 } else { acc })
-        })
+        self.cells.count(|cell| *cell > 0)
     }
 
     pub fn total_brightness(&self) -> usize {
-        self.cells
-            .iter()
-            .fold(0, |acc, row| acc + row.iter().sum::<usize>())
+        self.cells.iter().sum()
     }
 }
 
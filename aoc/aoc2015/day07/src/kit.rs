@@ -1,178 +1,832 @@
-use std::{collections::HashMap, fmt::Display, vec};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    fmt::Display,
+};
 
 pub type Result<T> = std::result::Result<T, KitError>;
 
-#[derive(Debug, Clone)]
-pub struct KitError {
-    message: String,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KitError {
+    /// An instruction that is not `<gate> -> <wire>`.
+    Parse { instruction: String, reason: String },
+    /// An operand that is neither a wire nor a signal, e.g. `x AND 1y`.
+    BadOperand { wire: String, operand: String },
+    /// A wire used as an input that no instruction or override provides.
+    UndefinedWire { wire: String, used_by: String },
+    /// A wire provided by more than one instruction.
+    DuplicateWire(String),
+    /// Wires whose signals depend on each other, sorted by name.
+    Cycle(Vec<String>),
+    /// A signal asked for a wire the kit does not have.
+    NoSuchWire(String),
+    /// A signal that does not fit the width of the kit's wires.
+    SignalTooWide { signal: u64, width: Width },
 }
 
 impl Display for KitError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[KitError]: {}", self.message)
+        write!(f, "[KitError]: ")?;
+        match self {
+            KitError::Parse {
+                instruction,
+                reason,
+            } => write!(f, "{} in {:?}", reason, instruction),
+            KitError::BadOperand { wire, operand } => {
+                write!(f, "bad operand {:?} for wire {}", operand, wire)
+            }
+            KitError::UndefinedWire { wire, used_by } => {
+                write!(f, "wire {} used by {} is not defined", wire, used_by)
+            }
+            KitError::DuplicateWire(wire) => write!(f, "wire {} is defined twice", wire),
+            KitError::Cycle(wires) => write!(f, "wires {} form a cycle", wires.join(", ")),
+            KitError::NoSuchWire(wire) => write!(f, "no wire {}", wire),
+            KitError::SignalTooWide { signal, width } => {
+                write!(f, "signal {} does not fit in {} bits", signal, width.bits())
+            }
+        }
     }
 }
 
-impl KitError {
-    fn new(msg: &str) -> Self {
-        Self {
-            message: msg.to_owned(),
+impl std::error::Error for KitError {}
+
+/// How many bits the signals of a kit's wires carry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Width {
+    W8,
+    #[default]
+    W16,
+    W32,
+    W64,
+}
+
+impl Width {
+    pub fn bits(self) -> u32 {
+        match self {
+            Width::W8 => 8,
+            Width::W16 => 16,
+            Width::W32 => 32,
+            Width::W64 => 64,
+        }
+    }
+
+    /// The largest signal a wire can carry.
+    pub fn mask(self) -> u64 {
+        u64::MAX >> (64 - self.bits())
+    }
+
+    fn check(self, signal: u64) -> Result<u64> {
+        match signal <= self.mask() {
+            true => Ok(signal),
+            false => Err(KitError::SignalTooWide {
+                signal,
+                width: self,
+            }),
         }
     }
 }
 
-#[derive(Debug)]
-enum Operator {
-    ASSIGN,
-    NOT,
-    AND,
-    OR,
-    LSHIFT,
-    RSHIFT,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operand {
+    Wire(String),
+    Signal(u64),
 }
 
-#[derive(Debug)]
+impl Operand {
+    fn parse(operand: &str, wire: &str, width: Width) -> Result<Self> {
+        if let Ok(signal) = operand.parse() {
+            return Ok(Operand::Signal(width.check(signal)?));
+        }
+        if !operand.is_empty() && operand.chars().all(|c| c.is_ascii_lowercase()) {
+            return Ok(Operand::Wire(operand.to_owned()));
+        }
+        Err(KitError::BadOperand {
+            wire: wire.to_owned(),
+            operand: operand.to_owned(),
+        })
+    }
+
+    fn wire(&self) -> Option<&str> {
+        match self {
+            Operand::Wire(wire) => Some(wire),
+            Operand::Signal(_) => None,
+        }
+    }
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operand::Wire(wire) => write!(f, "{}", wire),
+            Operand::Signal(signal) => write!(f, "{}", signal),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Gate {
+    Assign(Operand),
+    Not(Operand),
+    And(Operand, Operand),
+    Or(Operand, Operand),
+    LShift(Operand, Operand),
+    RShift(Operand, Operand),
+}
+
+impl Gate {
+    fn inputs(&self) -> Vec<&Operand> {
+        match self {
+            Gate::Assign(a) | Gate::Not(a) => vec![a],
+            Gate::And(a, b) | Gate::Or(a, b) | Gate::LShift(a, b) | Gate::RShift(a, b) => {
+                vec![a, b]
+            }
+        }
+    }
+}
+
+impl Display for Gate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Gate::Assign(a) => write!(f, "{}", a),
+            Gate::Not(a) => write!(f, "NOT {}", a),
+            Gate::And(a, b) => write!(f, "{} AND {}", a, b),
+            Gate::Or(a, b) => write!(f, "{} OR {}", a, b),
+            Gate::LShift(a, by) => write!(f, "{} LSHIFT {}", a, by),
+            Gate::RShift(a, by) => write!(f, "{} RSHIFT {}", a, by),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 struct Wire {
     title: String,
-    operator: Operator,
-    inputs: Vec<String>,
+    gate: Gate,
 }
 
 impl Wire {
-    fn parse(instruction: &str) -> Result<Self> {
-        let mut split = instruction.trim().split("->");
-        let inputs = split.next();
-        let output = split.next();
-        if inputs.is_none() || output.is_none() {
-            return Err(KitError::new("Failed to parse instruction"));
-        }
-        let input_parts: Vec<String> = inputs
-            .unwrap()
-            .trim()
-            .split(" ")
-            .map(|p| p.trim().to_owned())
-            .collect();
-        let operator = match input_parts.len() {
-            1 => Operator::ASSIGN,
-            2 => match input_parts[0].as_ref() {
-                "NOT" => Operator::NOT,
-                _ => return Err(KitError::new("Unknown operator")),
-            },
-            3 => match input_parts[1].as_ref() {
-                "AND" => Operator::AND,
-                "OR" => Operator::OR,
-                "LSHIFT" => Operator::LSHIFT,
-                "RSHIFT" => Operator::RSHIFT,
-                _ => return Err(KitError::new("Unknown operator")),
-            },
-            _ => return Err(KitError::new("Invalid instruction")),
+    fn parse(instruction: &str, width: Width) -> Result<Self> {
+        let error = |reason: &str| KitError::Parse {
+            instruction: instruction.trim().to_owned(),
+            reason: reason.to_owned(),
         };
-        let inputs = match operator {
-            Operator::ASSIGN => vec![input_parts[0].clone()],
-            Operator::NOT => vec![input_parts[1].clone()],
-            Operator::AND | Operator::OR | Operator::LSHIFT | Operator::RSHIFT => {
-                vec![input_parts[0].clone(), input_parts[2].clone()]
-            }
+        let (inputs, output) = instruction
+            .trim()
+            .split_once("->")
+            .ok_or_else(|| error("missing `->`"))?;
+        let title = output.trim();
+        if title.is_empty() || !title.chars().all(|c| c.is_ascii_lowercase()) {
+            return Err(error("invalid output wire"));
+        }
+        let operand = |operand: &str| Operand::parse(operand, title, width);
+
+        let parts: Vec<&str> = inputs.split_whitespace().collect();
+        let gate = match parts[..] {
+            [a] => Gate::Assign(operand(a)?),
+            ["NOT", a] => Gate::Not(operand(a)?),
+            [a, "AND", b] => Gate::And(operand(a)?, operand(b)?),
+            [a, "OR", b] => Gate::Or(operand(a)?, operand(b)?),
+            [a, "LSHIFT", by] => Gate::LShift(operand(a)?, operand(by)?),
+            [a, "RSHIFT", by] => Gate::RShift(operand(a)?, operand(by)?),
+            [_, _] | [_, _, _] => return Err(error("unknown operator")),
+            _ => return Err(error("invalid instruction")),
         };
         Ok(Wire {
-            title: output.unwrap().trim().to_owned(),
-            operator,
-            inputs,
+            title: title.to_owned(),
+            gate,
         })
     }
 }
 
+/// A circuit of wires, each getting its signal from a gate or, when
+/// overridden, from a fixed signal. Signals are 16 bits wide unless the kit
+/// is made [`Kit::with_width`].
+///
+/// Once evaluated, the signals are kept up to date when overrides change by
+/// recomputing only the wires downstream of the overridden one.
+#[derive(Debug, Clone, Default)]
 pub struct Kit {
-    wires: Vec<Wire>,
-    values: HashMap<String, u16>,
+    width: Width,
+    wires: BTreeMap<String, Wire>,
+    /// The wires each wire is an input of, whether overridden or not.
+    outputs: BTreeMap<String, Vec<String>>,
+    overrides: BTreeMap<String, u64>,
+    values: HashMap<String, u64>,
 }
 
 impl Kit {
     pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_width(width: Width) -> Self {
         Self {
-            wires: Vec::new(),
-            values: HashMap::new(),
+            width,
+            ..Self::default()
         }
     }
 
-    pub fn get_signal(&self, name: &str) -> Result<u16> {
-        self.values
-            .get(name)
-            .cloned()
-            .ok_or(KitError::new("Signal not found"))
+    pub fn width(&self) -> Width {
+        self.width
     }
 
-    pub fn process_kit(&mut self, initial_state: &HashMap<String, u16>) {
-        self.values = initial_state.clone();
-        let mut modified: HashMap<String, u16> = HashMap::new();
-
-        for wire in &self.wires {
-            if let Some(value) = self.process_wire(wire) {
-                modified.insert(wire.title.clone(), value);
+    pub fn add_instruction(&mut self, instruction: &str) -> Result<()> {
+        let wire = Wire::parse(instruction, self.width)?;
+        if self.wires.contains_key(&wire.title) {
+            return Err(KitError::DuplicateWire(wire.title));
+        }
+        for input in wire.gate.inputs().into_iter().filter_map(Operand::wire) {
+            let outputs = self.outputs.entry(input.to_owned()).or_default();
+            if !outputs.contains(&wire.title) {
+                outputs.push(wire.title.clone());
             }
         }
+        self.values.clear();
+        self.wires.insert(wire.title.clone(), wire);
+        Ok(())
+    }
 
-        for (key, value) in modified.iter() {
-            self.update_wires(key, *value);
+    /// Feeds `signal` to `wire` instead of the signal of its gate. Returns the
+    /// wires whose signals were recomputed, in evaluation order, none if the
+    /// kit was not evaluated yet.
+    pub fn set_override(&mut self, wire: &str, signal: u64) -> Result<Vec<String>> {
+        let signal = self.width.check(signal)?;
+        self.overrides.insert(wire.to_owned(), signal);
+        match self.values.is_empty() {
+            true => Ok(Vec::new()),
+            false => self.recompute(wire),
         }
     }
 
-    pub fn add_instruction(&mut self, instruction: &str) {
-        let wire = Wire::parse(instruction).unwrap();
-        self.wires.push(wire);
+    /// Gives `wire` the signal of its gate again, recomputing the wires like
+    /// [`Kit::set_override`]. Fails, keeping the override, if the gate reads
+    /// undefined wires or wires that depend on `wire`, or if `wire` has no
+    /// gate but feeds other wires.
+    pub fn clear_override(&mut self, wire: &str) -> Result<Vec<String>> {
+        let Some(signal) = self.overrides.remove(wire) else {
+            return Ok(Vec::new());
+        };
+        if self.values.is_empty() {
+            return Ok(Vec::new());
+        }
+        let checked = self.check_gate(wire);
+        if checked.is_err() {
+            self.overrides.insert(wire.to_owned(), signal);
+        }
+        match checked? {
+            true => self.recompute(wire),
+            false => {
+                self.values.remove(wire);
+                Ok(Vec::new())
+            }
+        }
     }
 
-    fn process_wire(&self, wire: &Wire) -> Option<u16> {
-        let get_value = |key: &str| -> Option<u16> {
-            key.parse::<u16>()
-                .ok()
-                .or_else(|| self.values.get(key).cloned())
+    /// Whether a wire that is no longer overridden has a gate, checking it
+    /// can be evaluated.
+    fn check_gate(&self, wire: &str) -> Result<bool> {
+        let Some(gate) = self.wires.get(wire).map(|w| &w.gate) else {
+            return match self.dependents(wire).into_iter().next() {
+                Some(used_by) => Err(KitError::UndefinedWire {
+                    wire: wire.to_owned(),
+                    used_by,
+                }),
+                None => Ok(false),
+            };
         };
+        for input in gate.inputs().into_iter().filter_map(Operand::wire) {
+            if !self.wires.contains_key(input) && !self.overrides.contains_key(input) {
+                return Err(KitError::UndefinedWire {
+                    wire: input.to_owned(),
+                    used_by: wire.to_owned(),
+                });
+            }
+        }
+        let dependencies = self.dependencies(wire)?;
+        if dependencies.contains(wire) {
+            let mut cycle: Vec<String> = self
+                .dependents(wire)
+                .intersection(&dependencies)
+                .cloned()
+                .collect();
+            if !cycle.iter().any(|w| w == wire) {
+                cycle.push(wire.to_owned());
+                cycle.sort();
+            }
+            return Err(KitError::Cycle(cycle));
+        }
+        Ok(true)
+    }
+
+    /// The wires `wire` currently reads its signal from: none if overridden.
+    fn wire_inputs(&self, wire: &str) -> Vec<&str> {
+        if self.overrides.contains_key(wire) {
+            return Vec::new();
+        }
+        match self.wires.get(wire) {
+            Some(w) => w
+                .gate
+                .inputs()
+                .into_iter()
+                .filter_map(Operand::wire)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// The wires currently reading the signal of `wire`.
+    fn wire_outputs(&self, wire: &str) -> impl Iterator<Item = &str> {
+        self.outputs
+            .get(wire)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .filter(|output| !self.overrides.contains_key(*output))
+    }
+
+    /// Every wire the signal of `wire` depends on, directly or not. Includes
+    /// `wire` only if it is part of a cycle.
+    pub fn dependencies(&self, wire: &str) -> Result<BTreeSet<String>> {
+        if !self.wires.contains_key(wire) && !self.overrides.contains_key(wire) {
+            return Err(KitError::NoSuchWire(wire.to_owned()));
+        }
+        let mut found = BTreeSet::new();
+        let mut stack = self.wire_inputs(wire);
+        while let Some(input) = stack.pop() {
+            if found.insert(input.to_owned()) {
+                stack.extend(self.wire_inputs(input));
+            }
+        }
+        Ok(found)
+    }
+
+    /// Every wire whose signal depends on `wire`, directly or not.
+    pub fn dependents(&self, wire: &str) -> BTreeSet<String> {
+        let mut found = BTreeSet::new();
+        let mut stack: Vec<&str> = self.wire_outputs(wire).collect();
+        while let Some(output) = stack.pop() {
+            if found.insert(output.to_owned()) {
+                stack.extend(self.wire_outputs(output));
+            }
+        }
+        found
+    }
 
-        if let Some(value) = self.values.get(&wire.title) {
-            return Some(*value);
-        }
-
-        match wire.operator {
-            Operator::ASSIGN => get_value(&wire.inputs[0]),
-            Operator::NOT => get_value(&wire.inputs[0]).and_then(|v| Some(!v)),
-            Operator::LSHIFT => get_value(&wire.inputs[0])
-                .and_then(|v| Some(v << &wire.inputs[1].parse::<u16>().unwrap())),
-            Operator::RSHIFT => get_value(&wire.inputs[0])
-                .and_then(|v| Some(v >> &wire.inputs[1].parse::<u16>().unwrap())),
-            Operator::AND => {
-                let v1 = get_value(&wire.inputs[0]);
-                let v2 = get_value(&wire.inputs[1]);
-                if let (Some(v1), Some(v2)) = (v1, v2) {
-                    Some(v1 & v2)
-                } else {
-                    None
+    /// Recomputes `wire` and the wires downstream of it, inputs first. The
+    /// other wires keep their signals.
+    fn recompute(&mut self, wire: &str) -> Result<Vec<String>> {
+        let mut cone = self.dependents(wire);
+        cone.insert(wire.to_owned());
+
+        let mut pending: HashMap<&str, usize> = cone
+            .iter()
+            .map(|title| {
+                let inputs = self.wire_inputs(title);
+                let count = inputs.iter().filter(|input| cone.contains(**input)).count();
+                (title.as_str(), count)
+            })
+            .collect();
+        let mut ready: VecDeque<&str> = pending
+            .iter()
+            .filter(|(_, &count)| count == 0)
+            .map(|(&title, _)| title)
+            .collect();
+        let mut order = Vec::with_capacity(cone.len());
+        while let Some(title) = ready.pop_front() {
+            order.push(title.to_owned());
+            for output in self.wire_outputs(title) {
+                if let Some(count) = pending.get_mut(output) {
+                    *count -= 1;
+                    if *count == 0 {
+                        ready.push_back(output);
+                    }
                 }
             }
-            Operator::OR => {
-                let v1 = get_value(&wire.inputs[0]);
-                let v2 = get_value(&wire.inputs[1]);
-                if let (Some(v1), Some(v2)) = (v1, v2) {
-                    Some(v1 | v2)
-                } else {
-                    None
+        }
+        if order.len() < cone.len() {
+            let mut cycle: Vec<String> = pending
+                .into_iter()
+                .filter(|(_, count)| *count > 0)
+                .map(|(title, _)| title.to_owned())
+                .collect();
+            cycle.sort();
+            return Err(KitError::Cycle(cycle));
+        }
+
+        for title in &order {
+            let value = match self.overrides.get(title) {
+                Some(signal) => *signal,
+                None => self.compute(&self.wires[title].gate, &self.values),
+            };
+            self.values.insert(title.clone(), value);
+        }
+        Ok(order)
+    }
+
+    pub fn overrides(&self) -> &BTreeMap<String, u64> {
+        &self.overrides
+    }
+
+    /// The signal of `name` as of the last [`Kit::evaluate`] and the overrides
+    /// changed since.
+    pub fn get_signal(&self, name: &str) -> Result<u64> {
+        self.values
+            .get(name)
+            .copied()
+            .ok_or_else(|| KitError::NoSuchWire(name.to_owned()))
+    }
+
+    /// The wires each wire takes its signal from. Overridden wires take it
+    /// from none.
+    fn inputs(&self) -> Result<BTreeMap<&str, Vec<&str>>> {
+        let mut inputs = BTreeMap::new();
+        for title in self.overrides.keys() {
+            inputs.insert(title.as_str(), Vec::new());
+        }
+        for (title, wire) in &self.wires {
+            if self.overrides.contains_key(title) {
+                continue;
+            }
+            let mut wires = Vec::new();
+            for input in wire.gate.inputs().into_iter().filter_map(Operand::wire) {
+                if !self.wires.contains_key(input) && !self.overrides.contains_key(input) {
+                    return Err(KitError::UndefinedWire {
+                        wire: input.to_owned(),
+                        used_by: title.clone(),
+                    });
                 }
+                wires.push(input);
             }
+            inputs.insert(title.as_str(), wires);
         }
+        Ok(inputs)
     }
 
-    fn update_wires(&mut self, key: &String, value: u16) {
-        if !self.values.contains_key(key) {
-            self.values.insert(key.clone(), value);
-            let mut modifications: HashMap<String, u16> = HashMap::new();
-            for wire in self.wires.iter().filter(|w| w.inputs.contains(key)) {
-                if let Some(value) = self.process_wire(wire) {
-                    modifications.insert(wire.title.clone(), value);
+    /// The wires ordered so that every wire comes after its inputs.
+    fn order(&self) -> Result<Vec<String>> {
+        let inputs = self.inputs()?;
+        let mut pending: HashMap<&str, usize> = HashMap::new();
+        let mut outputs: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (&title, wires) in &inputs {
+            pending.insert(title, wires.len());
+            for &input in wires {
+                outputs.entry(input).or_default().push(title);
+            }
+        }
+
+        let mut ready: VecDeque<&str> = pending
+            .iter()
+            .filter(|(_, &count)| count == 0)
+            .map(|(&title, _)| title)
+            .collect();
+        let mut order = Vec::with_capacity(inputs.len());
+        while let Some(title) = ready.pop_front() {
+            order.push(title.to_owned());
+            for &output in outputs.get(title).into_iter().flatten() {
+                let count = pending.get_mut(output).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    ready.push_back(output);
                 }
             }
-            for (k, v) in modifications {
-                self.update_wires(&k, v);
+        }
+
+        if order.len() < inputs.len() {
+            let mut cycle: Vec<String> = pending
+                .into_iter()
+                .filter(|(_, count)| *count > 0)
+                .map(|(title, _)| title.to_owned())
+                .collect();
+            cycle.sort();
+            return Err(KitError::Cycle(cycle));
+        }
+        Ok(order)
+    }
+
+    /// Computes the signal of every wire, each once, inputs first.
+    pub fn evaluate(&mut self) -> Result<()> {
+        let order = self.order()?;
+        let mut values = HashMap::with_capacity(order.len());
+        for title in order {
+            let value = match self.overrides.get(&title) {
+                Some(signal) => *signal,
+                None => self.compute(&self.wires[&title].gate, &values),
+            };
+            values.insert(title, value);
+        }
+        self.values = values;
+        Ok(())
+    }
+
+    /// The signal out of `gate`, whose input wires are already in `values`.
+    /// Shifting by the width of the kit or more gives 0.
+    fn compute(&self, gate: &Gate, values: &HashMap<String, u64>) -> u64 {
+        let value = |operand: &Operand| match operand {
+            Operand::Wire(wire) => values[wire],
+            Operand::Signal(signal) => *signal,
+        };
+        let shift = |by: &Operand| {
+            u32::try_from(value(by))
+                .ok()
+                .filter(|&by| by < self.width.bits())
+        };
+        let signal = match gate {
+            Gate::Assign(a) => value(a),
+            Gate::Not(a) => !value(a),
+            Gate::And(a, b) => value(a) & value(b),
+            Gate::Or(a, b) => value(a) | value(b),
+            Gate::LShift(a, by) => shift(by).map_or(0, |by| value(a) << by),
+            Gate::RShift(a, by) => shift(by).map_or(0, |by| value(a) >> by),
+        };
+        signal & self.width.mask()
+    }
+
+    /// The circuit as a Graphviz graph, with an edge from every wire to the
+    /// wires it feeds. Overridden wires are drawn as boxes without inputs.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph kit {\n");
+        for (title, wire) in &self.wires {
+            let label = match self.overrides.get(title) {
+                Some(signal) => format!("{} = {} (overridden)", title, signal),
+                None => format!("{} = {}", title, wire.gate),
+            };
+            let shape = match self.overrides.contains_key(title) {
+                true => "box",
+                false => "ellipse",
+            };
+            dot += &format!("    {} [label=\"{}\", shape={}];\n", title, label, shape);
+        }
+        for (title, signal) in &self.overrides {
+            if !self.wires.contains_key(title) {
+                dot += &format!(
+                    "    {} [label=\"{} = {} (overridden)\", shape=box];\n",
+                    title, title, signal
+                );
+            }
+        }
+        for (title, wire) in &self.wires {
+            if self.overrides.contains_key(title) {
+                continue;
             }
+            for input in wire.gate.inputs().into_iter().filter_map(Operand::wire) {
+                dot += &format!("    {} -> {};\n", input, title);
+            }
+        }
+        dot + "}\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kit(instructions: &[&str]) -> Kit {
+        let mut kit = Kit::new();
+        for instruction in instructions {
+            kit.add_instruction(instruction).unwrap();
+        }
+        kit
+    }
+
+    /// An evaluated kit with some wires overridden.
+    fn kit_with(instructions: &[&str], overrides: &[(&str, u64)]) -> Kit {
+        let mut kit = kit(instructions);
+        for (wire, signal) in overrides {
+            kit.set_override(wire, *signal).unwrap();
         }
+        let _ = kit.evaluate();
+        kit
+    }
+
+    fn example() -> Kit {
+        // listed outputs first, which the evaluation order must not depend on
+        kit(&[
+            "NOT y -> i",
+            "NOT x -> h",
+            "y RSHIFT 2 -> g",
+            "x LSHIFT 2 -> f",
+            "x OR y -> e",
+            "x AND y -> d",
+            "456 -> y",
+            "123 -> x",
+        ])
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let mut kit = example();
+        kit.evaluate().unwrap();
+        let signals = [
+            ("d", 72),
+            ("e", 507),
+            ("f", 492),
+            ("g", 114),
+            ("h", 65412),
+            ("i", 65079),
+            ("x", 123),
+            ("y", 456),
+        ];
+        for (wire, signal) in signals {
+            assert_eq!(kit.get_signal(wire), Ok(signal), "wire {}", wire);
+        }
+        assert_eq!(kit.get_signal("a"), Err(KitError::NoSuchWire("a".into())));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let mut kit = Kit::new();
+        assert!(matches!(
+            kit.add_instruction("x AND y"),
+            Err(KitError::Parse { .. })
+        ));
+        assert!(matches!(
+            kit.add_instruction("x XOR y -> z"),
+            Err(KitError::Parse { .. })
+        ));
+        assert_eq!(
+            kit.add_instruction("x AND 1y -> z"),
+            Err(KitError::BadOperand {
+                wire: "z".into(),
+                operand: "1y".into()
+            })
+        );
+        assert!(matches!(
+            kit.add_instruction("x LSHIFT 70000 -> z"),
+            Err(KitError::SignalTooWide { .. })
+        ));
+        kit.add_instruction("1 -> z").unwrap();
+        assert_eq!(
+            kit.add_instruction("2 -> z"),
+            Err(KitError::DuplicateWire("z".into()))
+        );
+    }
+
+    #[test]
+    fn test_undefined_wire() {
+        let mut kit = kit(&["x AND y -> z", "1 -> x"]);
+        assert_eq!(
+            kit.evaluate(),
+            Err(KitError::UndefinedWire {
+                wire: "y".into(),
+                used_by: "z".into()
+            })
+        );
+        kit.set_override("y", 3).unwrap();
+        kit.evaluate().unwrap();
+        assert_eq!(kit.get_signal("z"), Ok(1));
+    }
+
+    #[test]
+    fn test_cycle() {
+        let mut kit = kit(&["b -> a", "a AND c -> b", "1 -> c", "c -> d"]);
+        assert_eq!(
+            kit.evaluate(),
+            Err(KitError::Cycle(vec!["a".into(), "b".into()]))
+        );
+        // an override cuts the wire from its inputs, breaking the cycle
+        kit.set_override("b", 7).unwrap();
+        kit.evaluate().unwrap();
+        assert_eq!(kit.get_signal("a"), Ok(7));
+    }
+
+    #[test]
+    fn test_overrides() {
+        let mut kit = example();
+        kit.set_override("x", 1).unwrap();
+        kit.evaluate().unwrap();
+        assert_eq!(kit.get_signal("f"), Ok(4));
+        assert_eq!(kit.overrides().get("x"), Some(&1));
+
+        kit.clear_override("x").unwrap();
+        assert_eq!(kit.get_signal("f"), Ok(492));
+    }
+
+    #[test]
+    fn test_incremental_overrides() {
+        let mut kit = example();
+        assert_eq!(kit.set_override("x", 1), Ok(Vec::new()));
+        kit.clear_override("x").unwrap();
+        kit.evaluate().unwrap();
+
+        // only x and the wires it feeds are recomputed, inputs first
+        let recomputed = kit.set_override("x", 1).unwrap();
+        assert_eq!(recomputed[0], "x");
+        let mut sorted = recomputed.clone();
+        sorted.sort();
+        assert_eq!(sorted, vec!["d", "e", "f", "h", "x"]);
+        assert_eq!(kit.get_signal("d"), Ok(0));
+        assert_eq!(kit.get_signal("h"), Ok(65534));
+        assert_eq!(kit.get_signal("i"), Ok(65079));
+
+        // overriding a gate's output cuts it from its inputs
+        kit.set_override("d", 7).unwrap();
+        assert_eq!(kit.set_override("x", 2).unwrap().len(), 4);
+        assert_eq!(kit.get_signal("d"), Ok(7));
+
+        kit.clear_override("d").unwrap();
+        kit.clear_override("x").unwrap();
+        let mut evaluated = example();
+        evaluated.evaluate().unwrap();
+        for wire in ["d", "e", "f", "g", "h", "i", "x", "y"] {
+            assert_eq!(kit.get_signal(wire), evaluated.get_signal(wire));
+        }
+    }
+
+    #[test]
+    fn test_clear_override_errors() {
+        let mut kit = kit(&["b -> a", "a AND c -> b", "1 -> c"]);
+        kit.set_override("b", 7).unwrap();
+        kit.evaluate().unwrap();
+        assert_eq!(
+            kit.clear_override("b"),
+            Err(KitError::Cycle(vec!["a".into(), "b".into()]))
+        );
+        assert_eq!(kit.get_signal("a"), Ok(7));
+        assert_eq!(kit.overrides().get("b"), Some(&7));
+
+        let mut kit = kit_with(&["x AND y -> z", "1 -> x"], &[("y", 3)]);
+        assert_eq!(
+            kit.clear_override("y"),
+            Err(KitError::UndefinedWire {
+                wire: "y".into(),
+                used_by: "z".into()
+            })
+        );
+        assert_eq!(kit.get_signal("z"), Ok(1));
+    }
+
+    #[test]
+    fn test_dependencies() {
+        let mut kit = kit(&[
+            "x AND y -> z",
+            "1 -> x",
+            "NOT z -> w",
+            "2 -> y",
+            "y OR 1 -> v",
+        ]);
+        let names = |wires: &[&str]| wires.iter().map(|w| w.to_string()).collect();
+        assert_eq!(kit.dependencies("w"), Ok(names(&["x", "y", "z"])));
+        assert_eq!(kit.dependencies("x"), Ok(BTreeSet::new()));
+        assert_eq!(kit.dependents("y"), names(&["v", "w", "z"]));
+        assert!(kit.dependencies("q").is_err());
+
+        kit.set_override("z", 0).unwrap();
+        assert_eq!(kit.dependencies("w"), Ok(names(&["z"])));
+        assert_eq!(kit.dependents("y"), names(&["v"]));
+
+        let kit = kit_with(&["b -> a", "a -> b"], &[]);
+        assert_eq!(kit.dependencies("a"), Ok(names(&["a", "b"])));
+    }
+
+    #[test]
+    fn test_wire_shifts() {
+        let mut kit = kit(&["3 -> n", "1 LSHIFT n -> x", "x RSHIFT y -> z", "20 -> y"]);
+        kit.evaluate().unwrap();
+        assert_eq!(kit.get_signal("x"), Ok(8));
+        // shifting a 16-bit signal by 20 leaves nothing
+        assert_eq!(kit.get_signal("z"), Ok(0));
+
+        kit.set_override("n", 15).unwrap();
+        kit.set_override("y", 14).unwrap();
+        kit.evaluate().unwrap();
+        assert_eq!(kit.get_signal("z"), Ok(2));
+    }
+
+    #[test]
+    fn test_widths() {
+        let instructions = ["200 -> x", "NOT x -> n", "x LSHIFT 1 -> l"];
+        let signals = |width| {
+            let mut kit = Kit::with_width(width);
+            for instruction in instructions {
+                kit.add_instruction(instruction).unwrap();
+            }
+            kit.evaluate().unwrap();
+            (kit.get_signal("n").unwrap(), kit.get_signal("l").unwrap())
+        };
+        assert_eq!(signals(Width::W8), (55, 144));
+        assert_eq!(signals(Width::W16), (65335, 400));
+        assert_eq!(signals(Width::W32), (u32::MAX as u64 - 200, 400));
+        assert_eq!(signals(Width::W64), (u64::MAX - 200, 400));
+
+        let mut kit = Kit::with_width(Width::W8);
+        assert_eq!(
+            kit.add_instruction("256 -> x"),
+            Err(KitError::SignalTooWide {
+                signal: 256,
+                width: Width::W8
+            })
+        );
+        assert!(kit.set_override("x", 300).is_err());
+        assert_eq!(Width::W64.mask(), u64::MAX);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut kit = kit(&["x AND 3 -> z", "1 -> x", "NOT z -> w"]);
+        kit.set_override("x", 5).unwrap();
+        let dot = kit.to_dot();
+        assert!(dot.starts_with("digraph kit {\n"));
+        assert!(dot.contains("    z [label=\"z = x AND 3\", shape=ellipse];\n"));
+        assert!(dot.contains("    x [label=\"x = 5 (overridden)\", shape=box];\n"));
+        assert!(dot.contains("    z -> w;\n    x -> z;\n"));
+        assert!(dot.ends_with("}\n"));
     }
 }
@@ -0,0 +1,37 @@
+mod kit;
+
+use std::fmt::Display;
+
+use kit::Kit;
+use utils::solution::{Result, Solution};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Kit;
+
+    /// Builds and evaluates the circuit, so that a broken one is reported
+    /// here rather than by the parts.
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut kit = Kit::new();
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            kit.add_instruction(line)?;
+        }
+        kit.evaluate()?;
+        kit.get_signal("a")?;
+        Ok(kit)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        input.get_signal("a").unwrap()
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        let mut kit = input.clone();
+        // the signal of a fits any wire of the kit, and only the wires
+        // downstream of b are recomputed
+        kit.set_override("b", input.get_signal("a").unwrap())
+            .unwrap();
+        kit.get_signal("a").unwrap()
+    }
+}
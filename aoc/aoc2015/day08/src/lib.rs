@@ -1,27 +1,29 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use std::fmt::Display;
 
-fn main() {
-    let file = File::open("input.txt").expect("Failed to read input.txt");
-    let buffer = BufReader::new(file);
+use utils::solution::{Result, Solution};
 
-    let mut total_difference = 0;
-    let mut total_new_representation_difference = 0;
+pub struct Day08;
 
-    for line in buffer.lines() {
-        let line = line.expect("Failed to read line");
-        let code_count = line.len();
-        let memory_count = process_memory_chars(&line);
-        total_difference += code_count - (memory_count) as usize;
+impl Solution for Day08 {
+    type Input = Vec<String>;
 
-        let new_representation = convert_to_new_representation(&line);
-        total_new_representation_difference += new_representation.len() - code_count;
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(str::to_owned).collect())
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        input
+            .iter()
+            .map(|line| line.len() - process_memory_chars(line) as usize)
+            .sum::<usize>()
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        input
+            .iter()
+            .map(|line| convert_to_new_representation(line).len() - line.len())
+            .sum::<usize>()
     }
-    println!("Total difference: {}", total_difference);
-    println!(
-        "Total difference with new representation: {}",
-        total_new_representation_difference
-    );
 }
 
 fn process_memory_chars(line: &str) -> u16 {
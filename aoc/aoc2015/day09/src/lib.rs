@@ -0,0 +1,153 @@
+use std::fmt::Display;
+
+use utils::graph::{Graph, Objective, Tour, TourError};
+use utils::solution::{Result, Solution};
+
+pub type CityMap = Graph<String>;
+
+/// The route of the trip visiting every city once, and its distance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trip {
+    pub distance: u32,
+    pub route: Vec<String>,
+}
+
+/// The shortest and the longest trip, planned while parsing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trips {
+    pub shortest: Trip,
+    pub longest: Trip,
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Trips;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut cities = Graph::new();
+        for line in input.lines() {
+            let (from, to, distance) = parse_line(line)?;
+            cities.add_undirected_edge(from, to, distance as i64);
+        }
+        Ok(Trips {
+            shortest: plan_trip(&cities, Objective::Min)?,
+            longest: plan_trip(&cities, Objective::Max)?,
+        })
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        input.shortest.distance
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        input.longest.distance
+    }
+}
+
+fn parse_line(line: &str) -> Result<(String, String, u32)> {
+    let error = || format!("expected `<city> to <city> = <distance>`, got {:?}", line);
+    let (routes, distance) = line.split_once(" = ").ok_or_else(error)?;
+    let (from, to) = routes.split_once(" to ").ok_or_else(error)?;
+    let distance = distance.trim().parse::<u32>()?;
+    Ok((from.trim().to_string(), to.trim().to_string(), distance))
+}
+
+/// The shortest or longest trip through every city, starting at any of them.
+/// Fails if the cities are not connected enough to visit them all, or too
+/// many to plan for.
+pub fn plan_trip(cities: &CityMap, objective: Objective) -> std::result::Result<Trip, TourError> {
+    let route = cities.held_karp(objective, Tour::Path)?;
+    Ok(Trip {
+        distance: route.cost as u32,
+        route: route
+            .nodes
+            .iter()
+            .map(|&city| cities.label(city).clone())
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trips(lines: &[&str]) -> Trips {
+        Day09::parse(&lines.join("\n")).unwrap()
+    }
+
+    /// Checks the trip in either direction, both being as long.
+    fn assert_trip(trip: &Trip, distance: u32, route: &[&str]) {
+        let mut trip = trip.clone();
+        assert_eq!(trip.distance, distance);
+        if trip.route.first().map(String::as_str) != route.first().copied() {
+            trip.route.reverse();
+        }
+        assert_eq!(trip.route, route);
+    }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            parse_line("London to Dublin = 464").unwrap(),
+            ("London".to_owned(), "Dublin".to_owned(), 464)
+        );
+        assert!(parse_line("London to Dublin").is_err());
+        assert!(parse_line("London to Dublin = far").is_err());
+    }
+
+    #[test]
+    fn test_example() {
+        let trips = trips(&[
+            "London to Dublin = 464",
+            "London to Belfast = 518",
+            "Dublin to Belfast = 141",
+        ]);
+        assert_trip(&trips.shortest, 605, &["London", "Dublin", "Belfast"]);
+        assert_trip(&trips.longest, 982, &["Dublin", "London", "Belfast"]);
+    }
+
+    /// Going to the nearest (or farthest) city next gives 101 at best (191
+    /// at most), whichever city the trip starts from.
+    #[test]
+    fn test_greedy_counterexample() {
+        let trips = trips(&[
+            "Faerun to Norrath = 60",
+            "Faerun to Tristram = 42",
+            "Faerun to Tambi = 45",
+            "Faerun to Snowdin = 50",
+            "Norrath to Tristram = 40",
+            "Norrath to Tambi = 6",
+            "Norrath to Snowdin = 59",
+            "Tristram to Tambi = 18",
+            "Tristram to Snowdin = 27",
+            "Tambi to Snowdin = 20",
+        ]);
+        assert_trip(
+            &trips.shortest,
+            95,
+            &["Faerun", "Tristram", "Snowdin", "Tambi", "Norrath"],
+        );
+        assert_trip(
+            &trips.longest,
+            194,
+            &["Tambi", "Faerun", "Snowdin", "Norrath", "Tristram"],
+        );
+    }
+
+    #[test]
+    fn test_unplannable_trips() {
+        let mut cities = CityMap::new();
+        cities.add_undirected_edge("London".to_owned(), "Dublin".to_owned(), 464);
+        cities.add_undirected_edge("Paris".to_owned(), "Rome".to_owned(), 1400);
+        assert_eq!(plan_trip(&cities, Objective::Min), Err(TourError::NoRoute));
+        let input = "London to Dublin = 464\nParis to Rome = 1400";
+        assert!(Day09::parse(input).is_err());
+
+        let input: Vec<_> = (0..21)
+            .map(|city| format!("C{} to C{} = 1", city, city + 1))
+            .collect();
+        let err = Day09::parse(&input.join("\n")).unwrap_err();
+        assert!(err.to_string().contains("too many"), "{}", err);
+    }
+}
@@ -1,10 +1,23 @@
-fn main() {
-    let input = "1113122113";
-    let result = iterate(input, 40);
-    println!("Sequence length after 40 iterations = {}", result.len());
+use std::fmt::Display;
 
-    let result = iterate(input, 50);
-    println!("Sequence length after 50 iterations = {}", result.len());
+use utils::solution::{Result, Solution};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.trim().to_owned())
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        iterate(input, 40).len()
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        iterate(input, 50).len()
+    }
 }
 
 fn iterate(input: &str, count: u32) -> String {
@@ -1,16 +1,29 @@
-fn main() {
-    // password requirements:
-    // 1. exactly 8 lowercase letters
-    // 2. increment until it's valid
-    // 3. straight of 1 increasing of 3 letters like 'acb' or 'xyz'
-    // 4. should not include 'i', 'o', 'l'
-    // 5. at least 2 overlapping pairs, like 'aa' or 'bb'
-
-    let input = "vzbxkghb";
-    let next = find_next(input);
-    println!("Next password = {}", next);
-    let next = find_next(&next);
-    println!("Next password = {}", next);
+use std::fmt::Display;
+
+use utils::solution::{Result, Solution};
+
+// password requirements:
+// 1. exactly 8 lowercase letters
+// 2. increment until it's valid
+// 3. straight of 1 increasing of 3 letters like 'acb' or 'xyz'
+// 4. should not include 'i', 'o', 'l'
+// 5. at least 2 overlapping pairs, like 'aa' or 'bb'
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.trim().to_owned())
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        find_next(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        find_next(&find_next(input))
+    }
 }
 
 fn find_next(input: &str) -> String {
@@ -1,19 +1,23 @@
-use std::fs::File;
-use std::io::BufReader;
+use std::fmt::Display;
 
-fn main() {
-    let file = File::open("input.json").expect("Failed to open input.json");
-    let buffer = BufReader::new(file);
+use utils::solution::{Result, Solution};
 
-    let data: serde_json::Value =
-        serde_json::from_reader(buffer).expect("Failed to read JSON file");
+pub struct Day12;
 
-    let sum = calculate_sum(&data, &Vec::new());
-    println!("Sum of all numbers = {}", sum);
+impl Solution for Day12 {
+    type Input = serde_json::Value;
 
-    let ignore_list = vec!["red"];
-    let sum = calculate_sum(&data, &ignore_list);
-    println!("Sum of the numbers ignoring {:?} = {}", ignore_list, &sum);
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(serde_json::from_str(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        calculate_sum(input, &Vec::new())
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        calculate_sum(input, &vec!["red"])
+    }
 }
 
 fn calculate_sum(data: &serde_json::Value, ignore_words: &Vec<&str>) -> i64 {
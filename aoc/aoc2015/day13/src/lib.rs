@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use utils::graph::{Graph, Objective, Tour};
+use utils::solution::{Result, Solution};
+
+pub type HappinessDB = HashMap<String, HashMap<String, i32>>;
+
+/// Seats `guest` next to everyone already invited. `happiness` tells, for each
+/// of them, how happy the guest and they would be sitting next to each other.
+pub fn add_guest<F>(records: &HappinessDB, guest: &str, happiness: F) -> HappinessDB
+where
+    F: Fn(&str) -> (i32, i32),
+{
+    let mut records = records.clone();
+    for person in people(&records) {
+        let (guest_happiness, person_happiness) = happiness(&person);
+        records
+            .entry(String::from(guest))
+            .or_default()
+            .insert(person.clone(), guest_happiness);
+        records
+            .entry(person)
+            .or_default()
+            .insert(String::from(guest), person_happiness);
+    }
+    records
+}
+
+/// Everyone in the records, sorted.
+fn people(records: &HappinessDB) -> Vec<String> {
+    let mut people: Vec<String> = records
+        .iter()
+        .flat_map(|(person, neighbors)| std::iter::once(person).chain(neighbors.keys()))
+        .cloned()
+        .collect();
+    people.sort();
+    people.dedup();
+    people
+}
+
+/// An arrangement around the table, the last person sitting next to the
+/// first, and the change in happiness it brings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seating {
+    pub happiness: i32,
+    pub order: Vec<String>,
+}
+
+/// The happiest seatings of the guests, without and with me, found while
+/// parsing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seatings {
+    pub guests: Seating,
+    pub with_me: Seating,
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Seatings;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut records = HappinessDB::new();
+
+        for line in input.lines() {
+            let (from, to, happiness) = parse_line(line)?;
+            records.entry(from).or_default().insert(to, happiness);
+        }
+        // I am indifferent to everyone, and everyone to me
+        let with_me = add_guest(&records, "ME", |_| (0, 0));
+        Ok(Seatings {
+            guests: best_seating(&records)?,
+            with_me: best_seating(&with_me)?,
+        })
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        input.guests.happiness
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        input.with_me.happiness
+    }
+}
+
+/// The happiest seating. A round table has no first seat, so the first
+/// person in alphabetical order is seated first and only the others are
+/// arranged, keeping the best arrangement of each set of seated people and
+/// the last of them. Fails if a relation is only known one way, if there is
+/// no way to seat everyone next to people they know, or if there are too
+/// many people to seat.
+pub fn best_seating(records: &HappinessDB) -> std::result::Result<Seating, String> {
+    let mut table = Graph::new();
+    for person in people(records) {
+        table.add_node(person);
+    }
+    for (person, neighbors) in records {
+        for (neighbor, happiness) in neighbors {
+            let back = records
+                .get(neighbor)
+                .and_then(|neighbors| neighbors.get(person))
+                .ok_or_else(|| format!("how {} feels next to {} is not known", neighbor, person))?;
+            if person < neighbor {
+                table.add_undirected_edge(
+                    person.clone(),
+                    neighbor.clone(),
+                    (happiness + back) as i64,
+                );
+            }
+        }
+    }
+
+    let route = table
+        .held_karp(Objective::Max, Tour::Cycle)
+        .map_err(|e| e.to_string())?;
+    let order: Vec<&str> = route
+        .nodes
+        .iter()
+        .map(|&person| table.label(person).as_str())
+        .collect();
+    Ok(Seating {
+        happiness: calculate_happiness(&order, records),
+        order: order.into_iter().map(str::to_owned).collect(),
+    })
+}
+
+fn calculate_happiness(place_order: &Vec<&str>, relations: &HappinessDB) -> i32 {
+    if place_order.len() <= 1 {
+        return 0;
+    }
+    let first = &place_order[0];
+    let mut prv = first;
+    let mut sum = 0;
+    for person in place_order.iter().skip(1) {
+        sum += relations[*prv][*person];
+        sum += relations[*person][*prv];
+        prv = person;
+    }
+    if place_order.len() > 2 {
+        sum += relations[*first][*prv];
+        sum += relations[*prv][*first]
+    }
+    sum
+}
+
+fn parse_line(line: &str) -> Result<(String, String, i32)> {
+    let error = || {
+        format!(
+            "expected `<name> would gain|lose <units> happiness units by sitting next to <name>.`, got {:?}",
+            line
+        )
+    };
+    let words: Vec<&str> = line
+        .trim()
+        .strip_suffix('.')
+        .ok_or_else(error)?
+        .split(' ')
+        .collect();
+    let [from, "would", status, level, "happiness", "units", "by", "sitting", "next", "to", to] =
+        words[..]
+    else {
+        return Err(error().into());
+    };
+    let level = match status {
+        "gain" => level.parse::<i32>()?,
+        "lose" => -level.parse::<i32>()?,
+        _ => return Err(error().into()),
+    };
+
+    Ok((from.to_owned(), to.to_owned(), level))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser_positive() {
+        let input = "Alice would gain 54 happiness units by sitting next to Bob.";
+        assert_eq!(
+            parse_line(input).unwrap(),
+            ("Alice".to_string(), "Bob".to_string(), 54)
+        );
+    }
+
+    #[test]
+    fn test_parser_negative() {
+        let input = "Bob would lose 63 happiness units by sitting next to David.";
+        assert_eq!(
+            parse_line(input).unwrap(),
+            ("Bob".to_string(), "David".to_string(), -63)
+        );
+    }
+
+    #[test]
+    fn test_parser_errors() {
+        assert!(
+            parse_line("Alice would gain many happiness units by sitting next to Bob.").is_err()
+        );
+        assert!(parse_line("Alice would win 54 happiness units by sitting next to Bob.").is_err());
+        assert!(parse_line("Alice would gain 54 happiness units by sitting next to Bob").is_err());
+        assert!(parse_line("Alice sits next to Bob.").is_err());
+        assert!(Day13::parse("Alice would gain 54 happiness units\n").is_err());
+    }
+
+    fn prepare_test_db() -> HappinessDB {
+        let lines = vec![
+            "Alice would gain 54 happiness units by sitting next to Bob.",
+            "Alice would lose 79 happiness units by sitting next to Carol.",
+            "Alice would lose 2 happiness units by sitting next to David.",
+            "Bob would gain 83 happiness units by sitting next to Alice.",
+            "Bob would lose 7 happiness units by sitting next to Carol.",
+            "Bob would lose 63 happiness units by sitting next to David.",
+            "Carol would lose 62 happiness units by sitting next to Alice.",
+            "Carol would gain 60 happiness units by sitting next to Bob.",
+            "Carol would gain 55 happiness units by sitting next to David.",
+            "David would gain 46 happiness units by sitting next to Alice.",
+            "David would lose 7 happiness units by sitting next to Bob.",
+            "David would gain 41 happiness units by sitting next to Carol.",
+        ];
+        let mut db = HappinessDB::new();
+        for line in lines {
+            let (from, to, lvl) = parse_line(line).unwrap();
+            db.entry(from).or_default().insert(to, lvl);
+        }
+        db
+    }
+
+    #[test]
+    fn test_calculate_happiness() {
+        let relations = prepare_test_db();
+
+        assert!(calculate_happiness(&vec!["Alice", "Bob", "Carol", "David"], &relations) == 330);
+        assert!(calculate_happiness(&vec![], &relations) == 0);
+        assert!(calculate_happiness(&vec!["Alice"], &relations) == 0);
+        assert!(calculate_happiness(&vec!["Alice", "Bob"], &relations) == 54 + 83);
+    }
+
+    #[test]
+    fn test_best_seating() {
+        //      +41 +46
+        // +55   David    -2
+        // Carol       Alice
+        // +60    Bob    +54
+        //      -7  +83
+        let relations = prepare_test_db();
+        let seating = best_seating(&relations).unwrap();
+        assert_eq!(seating.happiness, 330);
+        let mut order = seating.order;
+        if order[1] != "Bob" {
+            order[1..].reverse();
+        }
+        assert_eq!(order, vec!["Alice", "Bob", "Carol", "David"]);
+    }
+
+    #[test]
+    fn test_add_guest() {
+        let relations = add_guest(&prepare_test_db(), "ME", |_| (0, 0));
+        assert_eq!(relations["ME"].len(), 4);
+        assert_eq!(relations["Carol"]["ME"], 0);
+        let seating = best_seating(&relations).unwrap();
+        assert_eq!(seating.order.len(), 5);
+        assert_eq!(seating.happiness, 286);
+
+        // a guest everyone loves sitting next to, sitting between the two
+        // people who like each other least
+        let relations = add_guest(&prepare_test_db(), "Eve", |person| match person {
+            "Alice" | "Carol" => (0, 100),
+            _ => (0, -100),
+        });
+        let seating = best_seating(&relations).unwrap();
+        let eve = seating.order.iter().position(|p| p == "Eve").unwrap();
+        let mut neighbors = [
+            seating.order[(eve + 1) % 5].as_str(),
+            seating.order[(eve + 4) % 5].as_str(),
+        ];
+        neighbors.sort();
+        assert_eq!(neighbors, ["Alice", "Carol"]);
+    }
+
+    #[test]
+    fn test_unknown_relation() {
+        let mut relations = prepare_test_db();
+        relations.get_mut("Bob").unwrap().remove("Alice");
+        assert!(best_seating(&relations).is_err());
+
+        let input = "Alice would gain 54 happiness units by sitting next to Bob.";
+        assert!(Day13::parse(input).is_err());
+    }
+}
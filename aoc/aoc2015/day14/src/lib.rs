@@ -1,27 +1,35 @@
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use std::fmt::Display;
 
-fn main() {
-    let file = File::open("input.txt").expect("Failed to open input.txt");
-    let buffer = BufReader::new(file);
+use utils::solution::{Result, Solution};
 
-    let end_time = 2503;
+const END_TIME: u32 = 2503;
 
-    let mut records: HashMap<String, (u32, u32, u32)> = HashMap::new();
+pub type Records = HashMap<String, (u32, u32, u32)>;
 
-    for line in buffer.lines() {
-        let line = line.expect("Failed to read line");
-        if let Some((deer, speed, duration, rest)) = parse_input(&line) {
-            records.insert(deer, (speed, duration, rest));
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Records;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut records = Records::new();
+
+        for line in input.lines() {
+            if let Some((deer, speed, duration, rest)) = parse_input(line) {
+                records.insert(deer, (speed, duration, rest));
+            }
         }
+        Ok(records)
     }
 
-    let (winner, distance) = perform_race(&records, end_time);
-    println!("winner is [{}] with distance = {}", winner, distance);
+    fn part1(input: &Self::Input) -> impl Display {
+        perform_race(input, END_TIME).1
+    }
 
-    let (winner, points) = perform_point_race(&records, end_time);
-    println!("winner is [{}] with points = {}", winner, points);
+    fn part2(input: &Self::Input) -> impl Display {
+        perform_point_race(input, END_TIME).1
+    }
 }
 
 fn perform_race(records: &HashMap<String, (u32, u32, u32)>, end_time: u32) -> (String, u32) {
@@ -1,8 +1,9 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use std::fmt::Display;
 use std::ops::Add;
 use std::vec;
 
+use utils::solution::{Result, Solution};
+
 #[derive(Debug, PartialEq)]
 struct Score {
     cap: i32,
@@ -46,7 +47,7 @@ impl PartialOrd for Score {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-struct Ingredient {
+pub struct Ingredient {
     name: String,
     cap: i32,
     dur: i32,
@@ -77,27 +78,22 @@ impl Ingredient {
     }
 }
 
-fn main() {
-    let file = File::open("input.txt").expect("Failed to open input.txt");
-    let buffer = BufReader::new(file);
+pub struct Day15;
 
-    let mut ingredients = Vec::new();
+impl Solution for Day15 {
+    type Input = Vec<Ingredient>;
 
-    for line in buffer.lines() {
-        let line = line.expect("Failed to read line");
-        if let Some(ingredient) = parse_input(&line) {
-            ingredients.push(ingredient);
-        }
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().filter_map(parse_input).collect())
     }
 
-    let total_score = make_optimal_cookie(&ingredients, 100);
-    println!("Total score for cookie = {}", total_score);
+    fn part1(input: &Self::Input) -> impl Display {
+        make_optimal_cookie(input, 100)
+    }
 
-    let total_score = make_optimal_cookie_with_calorie_limit(&ingredients, 100, 500);
-    println!(
-        "Total score for cookie with {} calories = {}",
-        500, total_score
-    );
+    fn part2(input: &Self::Input) -> impl Display {
+        make_optimal_cookie_with_calorie_limit(input, 100, 500)
+    }
 }
 
 fn parse_input(line: &str) -> Option<Ingredient> {
@@ -1,8 +1,11 @@
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use std::fmt::Display;
 
-fn main() {
+use utils::solution::{Result, Solution};
+
+pub type AuntDB = HashMap<usize, HashMap<String, u32>>;
+
+fn interests() -> HashMap<String, u32> {
     let mut interests: HashMap<String, u32> = HashMap::new();
     interests.insert("children".to_string(), 3);
     interests.insert("cats".to_string(), 7);
@@ -14,23 +17,31 @@ fn main() {
     interests.insert("trees".to_string(), 3);
     interests.insert("cars".to_string(), 2);
     interests.insert("perfumes".to_string(), 1);
+    interests
+}
+
+pub struct Day16;
 
-    let file = File::open("input.txt").expect("Failed to open input.txt");
-    let buffer = BufReader::new(file);
+impl Solution for Day16 {
+    type Input = AuntDB;
 
-    let mut aunt_db: HashMap<usize, HashMap<String, u32>> = HashMap::new();
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut aunt_db = AuntDB::new();
 
-    for (idx, line) in buffer.lines().enumerate() {
-        let line = line.expect("Failed to read line");
-        let aunt_interests = parse_aunt(&line);
-        aunt_db.insert(idx + 1, aunt_interests);
+        for (idx, line) in input.lines().enumerate() {
+            let aunt_interests = parse_aunt(line);
+            aunt_db.insert(idx + 1, aunt_interests);
+        }
+        Ok(aunt_db)
     }
 
-    let selected_aunt = find_aunt(&interests, &aunt_db);
-    println!("Selected aunt = {}", selected_aunt);
+    fn part1(input: &Self::Input) -> impl Display {
+        find_aunt(&interests(), input)
+    }
 
-    let selected_aunt = find_aunt_with_adjusted_rules(&interests, &aunt_db);
-    println!("Selected aunt with new rules = {}", selected_aunt);
+    fn part2(input: &Self::Input) -> impl Display {
+        find_aunt_with_adjusted_rules(&interests(), input)
+    }
 }
 
 fn find_aunt(
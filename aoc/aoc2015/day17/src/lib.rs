@@ -1,44 +1,43 @@
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fmt::Display;
+
+use utils::parse;
+use utils::solution::{Result, Solution};
 
 type Variant = HashSet<usize>;
 
-fn main() {
-    let file = File::open("input.txt").expect("Failed to open input.txt");
-    let buffer = BufReader::new(file);
+const TOTAL: u32 = 150;
+
+pub struct Day17;
 
-    let mut containers = Vec::new();
+impl Solution for Day17 {
+    type Input = Vec<u32>;
 
-    for line in buffer.lines() {
-        let container = line
-            .expect("Failed to read line")
-            .parse::<u32>()
-            .expect("Failed to parse container");
-        containers.push(container);
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::numbers(input)?)
     }
 
-    let variants = generate_variants(150, &containers, Variant::new());
-    println!("Variant count = {}", variants.len());
+    fn part1(input: &Self::Input) -> impl Display {
+        generate_variants(TOTAL, input, Variant::new()).len()
+    }
 
-    let mut min_len = std::usize::MAX;
-    let mut min_count = 0;
+    fn part2(input: &Self::Input) -> impl Display {
+        let variants = generate_variants(TOTAL, input, Variant::new());
 
-    for variant in variants {
-        let len = variant.len();
-        if len < min_len {
-            min_len = len;
-            min_count = 1;
-        } else if len == min_len {
-            min_count += 1;
+        let mut min_len = std::usize::MAX;
+        let mut min_count = 0;
+
+        for variant in variants {
+            let len = variant.len();
+            if len < min_len {
+                min_len = len;
+                min_count = 1;
+            } else if len == min_len {
+                min_count += 1;
+            }
         }
+        min_count
     }
-    // Variant count = 654
-    // minimum required container count is 4 with 57 variants
-    println!(
-        "minimum required container count is {} with {} variants",
-        min_len, min_count
-    );
 }
 
 fn generate_variants(total: u32, containers: &[u32], filled: Variant) -> Vec<Variant> {
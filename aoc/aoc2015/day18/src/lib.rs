@@ -0,0 +1,206 @@
+use std::fmt::Display;
+
+use utils::grid::{Adjacency, Edges, Grid};
+use utils::solution::{self, Solution};
+
+fn count_lights(grid: &Grid<bool>) -> usize {
+    grid.count(|on| *on)
+}
+
+fn iterate(grid: &Grid<bool>) -> Grid<bool> {
+    let mut next = grid.clone();
+    for ((x, y), _) in grid.enumerate() {
+        next[(x, y)] = calculate_status(grid, x, y);
+    }
+    next
+}
+
+fn calculate_status(grid: &Grid<bool>, x: usize, y: usize) -> bool {
+    let current_status = grid[(x, y)];
+    let neighbors = count_neighbors(grid, x, y);
+    match (current_status, neighbors) {
+        (true, 2 | 3) => true,
+        (true, _) => false,
+        (false, 3) => true,
+        (false, _) => false,
+    }
+}
+
+fn count_neighbors(grid: &Grid<bool>, x: usize, y: usize) -> usize {
+    grid.neighbors((x, y), Adjacency::Eight, Edges::Clamp)
+        .filter(|pos| grid[*pos])
+        .count()
+}
+
+fn stick_corners(grid: &mut Grid<bool>) {
+    let last_col = grid.width() - 1;
+    let last_row = grid.height() - 1;
+    for corner in [(0, 0), (last_col, 0), (0, last_row), (last_col, last_row)] {
+        grid[corner] = true;
+    }
+}
+
+fn iterate_with_stuck_lights(grid: &Grid<bool>) -> Grid<bool> {
+    let mut next = iterate(grid);
+    stick_corners(&mut next);
+    next
+}
+
+const STEPS: usize = 100;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Grid<bool>;
+
+    fn parse(input: &str) -> solution::Result<Self::Input> {
+        Ok(Grid::parse(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        let mut grid = input.clone();
+        for _ in 0..STEPS {
+            grid = iterate(&grid);
+        }
+        count_lights(&grid)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        let mut grid = input.clone();
+        stick_corners(&mut grid);
+
+        for _ in 0..STEPS {
+            grid = iterate_with_stuck_lights(&grid);
+        }
+        count_lights(&grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_parser() {
+        let grid = Grid::parse(".#.#.#").unwrap();
+        assert_eq!(
+            grid.iter().copied().collect::<Vec<_>>(),
+            vec![false, true, false, true, false, true]
+        );
+        assert!(Grid::parse(".#.#.#$").is_err());
+    }
+
+    #[test]
+    fn test_parser() {
+        let grid = Grid::parse(
+            r#"##.#.#
+            ...##.
+            #....#
+            ..#...
+            #.#..#
+            ####.#"#,
+        );
+
+        let created = Grid::from_rows(vec![
+            vec![true, true, false, true, false, true],    // ##.#.#
+            vec![false, false, false, true, true, false],  // ...##.
+            vec![true, false, false, false, false, true],  // #....#
+            vec![false, false, true, false, false, false], // ..#...
+            vec![true, false, true, false, false, true],   // #.#..#
+            vec![true, true, true, true, false, true],     // ####.#
+        ])
+        .unwrap();
+        assert_eq!(grid, Ok(created));
+    }
+
+    #[test]
+    fn test_count_neighbors() {
+        let grid = Grid::from_rows(vec![
+            vec![false, true, false],
+            vec![true, false, true],
+            vec![false, true, false],
+        ])
+        .unwrap();
+        assert!(count_neighbors(&grid, 0, 0) == 2);
+        assert!(count_neighbors(&grid, 0, 1) == 2);
+        assert!(count_neighbors(&grid, 0, 2) == 2);
+        assert!(count_neighbors(&grid, 1, 0) == 2);
+        assert!(count_neighbors(&grid, 1, 1) == 4);
+        assert!(count_neighbors(&grid, 1, 2) == 2);
+        assert!(count_neighbors(&grid, 2, 0) == 2);
+        assert!(count_neighbors(&grid, 2, 1) == 2);
+        assert!(count_neighbors(&grid, 2, 2) == 2);
+    }
+
+    #[test]
+    fn test_calculate_status() {
+        let grid = Grid::from_rows(vec![
+            vec![false, true, false, true],
+            vec![true, false, true, false],
+            vec![false, true, true, false],
+            vec![false, true, false, false],
+        ])
+        .unwrap();
+
+        assert!(calculate_status(&grid, 0, 0) == false);
+        assert!(calculate_status(&grid, 1, 0) == true);
+        assert!(calculate_status(&grid, 2, 2) == true);
+        assert!(calculate_status(&grid, 0, 2) == true);
+        assert!(calculate_status(&grid, 0, 2) == true);
+        assert!(calculate_status(&grid, 3, 0) == false);
+    }
+
+    #[test]
+    fn test_iteration() {
+        let grid = Grid::parse(
+            r#".#.#.#
+               ...##.
+               #....#
+               ..#...
+               #.#..#
+               ####.."#,
+        )
+        .unwrap();
+        println!("{}", grid);
+
+        let grid = iterate(&grid);
+
+        let next = Grid::parse(
+            r#"..##..
+               ..##.#
+               ...##.
+               ......
+               #.....
+               #.##.."#,
+        )
+        .unwrap();
+        assert_eq!(grid, next);
+    }
+
+    #[test]
+    fn test_iteration_with_stuck_lights() {
+        let grid = Grid::parse(
+            r#"##.#.#
+               ...##.
+               #....#
+               ..#...
+               #.#..#
+               ####.#"#,
+        )
+        .unwrap();
+        println!("{}", grid);
+
+        let grid = iterate_with_stuck_lights(&grid);
+
+        let next = Grid::parse(
+            r#"#.##.#
+            ####.#
+            ...##.
+            ......
+            #...#.
+            #.####"#,
+        )
+        .unwrap();
+        assert_eq!(grid, next);
+    }
+}
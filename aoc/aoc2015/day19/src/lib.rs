@@ -1,33 +1,40 @@
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use std::fmt::Display;
 
 use rand::prelude::SliceRandom;
 use rand::thread_rng;
+use utils::solution::{Result, Solution};
 
-type Instruction = (String, String);
+pub type Instruction = (String, String);
 
-fn main() {
-    let file = File::open("input.txt").expect("Failed to open input.txt");
-    let buffer = BufReader::new(file);
+pub struct Day19;
 
-    let mut instructions = Vec::new();
-    let mut source = String::new();
+impl Solution for Day19 {
+    type Input = (String, Vec<Instruction>);
 
-    for line in buffer.lines() {
-        let line = line.expect("Failed to read line");
-        if let Some(instruction) = parse_line(&line) {
-            instructions.push(instruction);
-        } else if line.trim().len() > 0 {
-            source = line;
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut instructions = Vec::new();
+        let mut source = String::new();
+
+        for line in input.lines() {
+            if let Some(instruction) = parse_line(line) {
+                instructions.push(instruction);
+            } else if line.trim().len() > 0 {
+                source = line.trim().to_owned();
+            }
         }
+        Ok((source, instructions))
     }
 
-    let variants = generate_variants(&source, &instructions);
-    println!("Number of generated molecules = {}", variants.len());
+    fn part1(input: &Self::Input) -> impl Display {
+        let (source, instructions) = input;
+        generate_variants(source, instructions).len()
+    }
 
-    let steps = make_molecule(&source, &instructions, "e");
-    println!("It takes {:?} steps to generate the molecule", steps);
+    fn part2(input: &Self::Input) -> impl Display {
+        let (source, instructions) = input;
+        make_molecule(source, instructions, "e")
+    }
 }
 
 fn parse_line(line: &str) -> Option<Instruction> {
@@ -69,7 +76,6 @@ fn generate_variants(source: &str, instructions: &[Instruction]) -> HashSet<Stri
 fn make_molecule(molecule: &str, instructions: &[Instruction], start: &str) -> usize {
     let mut mutations = 0;
 
-    let mut cycles = 0;
     let mut instructions = instructions.to_vec();
     let mut target = molecule.to_owned();
 
@@ -88,10 +94,6 @@ fn make_molecule(molecule: &str, instructions: &[Instruction], start: &str) -> u
             target = molecule.to_owned();
             mutations = 0;
             instructions.shuffle(&mut thread_rng());
-            cycles += 1;
-            if cycles % 1_000_000 == 0 {
-                println!("cycles: {}", cycles);
-            }
         }
     }
 
@@ -1,24 +1,29 @@
 use std::collections::HashSet;
+use std::fmt::Display;
 
-fn main() {
-    let input = 29_000_000;
+use utils::solution::{Result, Solution};
 
-    let house_no = find_house(input, 10, -1);
-    println!(
-        "House no. {} is the first that receives {} presents.",
-        house_no, input
-    );
+pub struct Day20;
 
-    let house_no = find_house(input, 11, 50);
-    println!("after re-arrange the house no. is: {}", house_no);
+impl Solution for Day20 {
+    type Input = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.trim().parse()?)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        find_house(*input, 10, -1)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        find_house(*input, 11, 50)
+    }
 }
 
 fn find_house(minimum_presents: usize, present_count: usize, limit: i32) -> usize {
     let mut house_no = 1;
     while calculate_presents(house_no, present_count, limit) < minimum_presents {
-        if house_no % 100_000 == 0 {
-            println!("{} houses calculated", house_no);
-        }
         house_no += 1;
     }
     house_no
@@ -1,7 +1,11 @@
 use std::cmp;
+use std::fmt::Display;
+
+use utils::parse;
+use utils::solution::{Result, Solution};
 
 #[derive(Debug)]
-struct Player {
+pub struct Player {
     hp: i32,
     c: i32,
     d: i32,
@@ -17,7 +21,7 @@ impl Player {
 // Cost  Damage  Armor
 type Instrument = (i32, i32, i32);
 
-fn main() {
+fn shop() -> (Vec<Instrument>, Vec<Instrument>, Vec<Instrument>) {
     let mut weapons = Vec::new();
     weapons.push((8, 4, 0));
     weapons.push((10, 5, 0));
@@ -43,10 +47,45 @@ fn main() {
     rings.push((0, 0, 0)); // not wearing ring L
     rings.push((0, 0, 0)); // not wearing ring R
 
-    let boss = Player::new(109, 0, 8, 2);
-    let (min_win, max_loose) = calculate_minimum_cost(100, &weapons, &armors, &rings, &boss);
-    println!("Min cost to win = {}", min_win);
-    println!("Max cost and still loose = {}", max_loose);
+    (weapons, armors, rings)
+}
+
+fn parse_boss(input: &str) -> Result<Player> {
+    let mut boss = Player::new(0, 0, 0, 0);
+    for (key, value) in parse::key_values(input)? {
+        match key {
+            "Hit Points" => boss.hp = value,
+            "Damage" => boss.d = value,
+            "Armor" => boss.a = value,
+            _ => return Err(format!("Unknown boss stat: {}", key).into()),
+        }
+    }
+    Ok(boss)
+}
+
+fn play(boss: &Player) -> (i32, i32) {
+    let (weapons, armors, rings) = shop();
+    calculate_minimum_cost(100, &weapons, &armors, &rings, boss)
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Player;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_boss(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        let (min_win, _) = play(input);
+        min_win
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        let (_, max_loose) = play(input);
+        max_loose
+    }
 }
 
 fn fight(player: &Player, boss: &Player) -> bool {
@@ -1,19 +1,49 @@
 mod player;
 mod spell;
 
+use std::fmt::Display;
+
 use player::*;
 use spell::*;
+use utils::parse;
+use utils::solution::{Result, Solution};
 
-fn main() {
+fn parse_boss(input: &str) -> Result<Player> {
+    let mut hp = 0;
+    let mut damage = 0;
+    for (key, value) in parse::key_values(input)? {
+        match key {
+            "Hit Points" => hp = value,
+            "Damage" => damage = value,
+            _ => return Err(format!("Unknown boss stat: {}", key).into()),
+        }
+    }
+    Ok(Player::new(hp, 0, damage))
+}
+
+fn optimal_mana(boss: &Player, hard: bool) -> isize {
     let player = Player::new(50, 500, 0);
-    let boss = Player::new(71, 0, 10);
     let mut min_mana = isize::max_value();
-    simulate_battle(&player, &boss, &mut min_mana, 0, false);
-    println!("optimal mana: {}", min_mana);
+    simulate_battle(&player, boss, &mut min_mana, 0, hard);
+    min_mana
+}
+
+pub struct Day22;
 
-    let mut min_mp = isize::max_value();
-    simulate_battle(&player, &boss, &mut min_mp, 0, true);
-    println!("optimal mana (hard): {}", min_mp);
+impl Solution for Day22 {
+    type Input = Player;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_boss(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        optimal_mana(input, false)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        optimal_mana(input, true)
+    }
 }
 
 fn simulate_battle(
@@ -0,0 +1,530 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    fmt::Display,
+    str::FromStr,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Instruction {
+    HLF(char),        // half
+    TPL(char),        // triple
+    INC(char),        // increment
+    JMP(isize),       // jump
+    JIE(char, isize), // jump if even
+    JIO(char, isize), // jump if one
+}
+
+impl FromStr for Instruction {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (cmd, args) = line.trim().split_once(' ').ok_or("missing operand")?;
+        let register = |arg: &str| match arg.trim() {
+            reg @ ("a" | "b") => Ok(reg.chars().next().unwrap()),
+            reg => Err(format!("unknown register {:?}", reg)),
+        };
+        let offset = |arg: &str| {
+            arg.trim()
+                .parse::<isize>()
+                .map_err(|_| format!("bad offset {:?}", arg.trim()))
+        };
+        let conditional = |args: &str| {
+            let (reg, count) = args.split_once(',').ok_or("missing offset")?;
+            Ok::<_, String>((register(reg)?, offset(count)?))
+        };
+
+        match cmd {
+            "hlf" => Ok(Instruction::HLF(register(args)?)),
+            "tpl" => Ok(Instruction::TPL(register(args)?)),
+            "inc" => Ok(Instruction::INC(register(args)?)),
+            "jmp" => Ok(Instruction::JMP(offset(args)?)),
+            "jie" => conditional(args).map(|(reg, count)| Instruction::JIE(reg, count)),
+            "jio" => conditional(args).map(|(reg, count)| Instruction::JIO(reg, count)),
+            _ => Err(format!("unknown instruction {:?}", cmd)),
+        }
+    }
+}
+
+/// Disassembles the instruction into the source it is parsed from.
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::HLF(reg) => write!(f, "hlf {}", reg),
+            Instruction::TPL(reg) => write!(f, "tpl {}", reg),
+            Instruction::INC(reg) => write!(f, "inc {}", reg),
+            Instruction::JMP(count) => write!(f, "jmp {:+}", count),
+            Instruction::JIE(reg, count) => write!(f, "jie {}, {:+}", reg, count),
+            Instruction::JIO(reg, count) => write!(f, "jio {}, {:+}", reg, count),
+        }
+    }
+}
+
+/// Parses a program, one instruction per line. Errors give the line number.
+pub fn assemble(source: &str) -> Result<Vec<Instruction>, String> {
+    source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            line.parse()
+                .map_err(|e| format!("line {}: {} in {:?}", idx + 1, e, line))
+        })
+        .collect()
+}
+
+/// The source of a program, which [`assemble`] turns back into it.
+pub fn disassemble(program: &[Instruction]) -> String {
+    program
+        .iter()
+        .map(|instruction| format!("{}\n", instruction))
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ExecutionError {
+    /// A jump to before the first instruction.
+    InvalidJump { pc: usize, instruction: Instruction },
+    /// A register growing past `usize::MAX`.
+    Overflow { pc: usize, instruction: Instruction },
+    /// A register other than `a` and `b`.
+    NoSuchRegister(char),
+    /// The computer came back to a state it was in before, so it never halts.
+    InfiniteLoop { pc: usize, registers: Registers },
+}
+
+impl Display for ExecutionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[ExecutionError]: ")?;
+        match self {
+            ExecutionError::InvalidJump { pc, instruction } => {
+                write!(f, "{} at {} jumps before the program", instruction, pc)
+            }
+            ExecutionError::Overflow { pc, instruction } => {
+                write!(f, "{} at {} overflows", instruction, pc)
+            }
+            ExecutionError::NoSuchRegister(reg) => write!(f, "no register {}", reg),
+            ExecutionError::InfiniteLoop { pc, registers } => {
+                write!(f, "infinite loop at {} with {}", pc, registers)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExecutionError {}
+
+pub type ExecutionResult<T> = Result<T, ExecutionError>;
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Registers {
+    pub a: usize,
+    pub b: usize,
+}
+
+impl Registers {
+    pub fn get(&self, reg: char) -> ExecutionResult<usize> {
+        match reg {
+            'a' => Ok(self.a),
+            'b' => Ok(self.b),
+            _ => Err(ExecutionError::NoSuchRegister(reg)),
+        }
+    }
+
+    fn get_mut(&mut self, reg: char) -> ExecutionResult<&mut usize> {
+        match reg {
+            'a' => Ok(&mut self.a),
+            'b' => Ok(&mut self.b),
+            _ => Err(ExecutionError::NoSuchRegister(reg)),
+        }
+    }
+}
+
+impl Display for Registers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "a={} b={}", self.a, self.b)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Computer {
+    pc: usize,
+    registers: Registers,
+}
+
+impl Computer {
+    pub fn new(reg_a: usize, reg_b: usize) -> Self {
+        Self {
+            pc: 0,
+            registers: Registers { a: reg_a, b: reg_b },
+        }
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn registers(&self) -> Registers {
+        self.registers
+    }
+
+    /// The instruction to execute next, none once the computer halted.
+    pub fn current<'p>(&self, program: &'p [Instruction]) -> Option<&'p Instruction> {
+        program.get(self.pc)
+    }
+
+    /// Executes the current instruction. Returns it, or none if the computer
+    /// halted.
+    pub fn step(&mut self, program: &[Instruction]) -> ExecutionResult<Option<Instruction>> {
+        let Some(&instruction) = self.current(program) else {
+            return Ok(None);
+        };
+        let overflow = ExecutionError::Overflow {
+            pc: self.pc,
+            instruction,
+        };
+        let mut step = 1;
+        match instruction {
+            Instruction::HLF(reg) => {
+                let target = self.registers.get_mut(reg)?;
+                *target /= 2;
+            }
+            Instruction::TPL(reg) => {
+                let target = self.registers.get_mut(reg)?;
+                *target = target.checked_mul(3).ok_or(overflow)?;
+            }
+            Instruction::INC(reg) => {
+                let target = self.registers.get_mut(reg)?;
+                *target = target.checked_add(1).ok_or(overflow)?;
+            }
+            Instruction::JMP(count) => {
+                step = count;
+            }
+            Instruction::JIE(reg, count) => {
+                if self.registers.get(reg)? % 2 == 0 {
+                    step = count;
+                }
+            }
+            Instruction::JIO(reg, count) => {
+                if self.registers.get(reg)? == 1 {
+                    step = count;
+                }
+            }
+        }
+
+        self.pc = self
+            .pc
+            .checked_add_signed(step)
+            .ok_or(ExecutionError::InvalidJump {
+                pc: self.pc,
+                instruction,
+            })?;
+        Ok(Some(instruction))
+    }
+
+    /// Fails if the computer is in a state of `seen`, remembering it otherwise.
+    /// A state repeating means the program loops forever: the next steps only
+    /// depend on it. Loops growing a register are not caught.
+    fn check_loop(&self, seen: &mut HashSet<(usize, Registers)>) -> ExecutionResult<()> {
+        match seen.insert((self.pc, self.registers)) {
+            true => Ok(()),
+            false => Err(ExecutionError::InfiniteLoop {
+                pc: self.pc,
+                registers: self.registers,
+            }),
+        }
+    }
+
+    pub fn run(&mut self, program: &[Instruction]) -> ExecutionResult<(usize, usize)> {
+        let mut seen = HashSet::new();
+        while self.current(program).is_some() {
+            self.check_loop(&mut seen)?;
+            self.step(program)?;
+        }
+        Ok((self.registers.a, self.registers.b))
+    }
+}
+
+/// An executed instruction and the registers it left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub pc: usize,
+    pub instruction: Instruction,
+    pub registers: Registers,
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>4}: {:<12} {}",
+            self.pc,
+            self.instruction.to_string(),
+            self.registers
+        )
+    }
+}
+
+/// Why the debugger gave control back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// A single instruction was executed.
+    Stepped,
+    /// The instruction at this address is a breakpoint and was not executed.
+    Breakpoint(usize),
+    /// A watched register changed.
+    Changed { reg: char, from: usize, to: usize },
+    /// The program ran past its last instruction.
+    Halted,
+}
+
+/// Runs a program on a computer one instruction at a time, stopping at
+/// breakpoints and when watched registers change, and failing on infinite
+/// loops like [`Computer::run`].
+pub struct Debugger<'p> {
+    program: &'p [Instruction],
+    computer: Computer,
+    breakpoints: BTreeSet<usize>,
+    watches: BTreeSet<char>,
+    trace: Option<Vec<Step>>,
+    seen: HashSet<(usize, Registers)>,
+    /// Whether the debugger stopped at the breakpoint of the current
+    /// instruction, which resuming then executes.
+    at_breakpoint: bool,
+}
+
+impl<'p> Debugger<'p> {
+    pub fn new(program: &'p [Instruction], computer: Computer) -> Self {
+        Self {
+            program,
+            computer,
+            breakpoints: BTreeSet::new(),
+            watches: BTreeSet::new(),
+            trace: None,
+            seen: HashSet::new(),
+            at_breakpoint: false,
+        }
+    }
+
+    pub fn computer(&self) -> &Computer {
+        &self.computer
+    }
+
+    pub fn into_computer(self) -> Computer {
+        self.computer
+    }
+
+    /// Stops before executing the instruction at `pc`.
+    pub fn add_breakpoint(&mut self, pc: usize) {
+        self.breakpoints.insert(pc);
+    }
+
+    pub fn remove_breakpoint(&mut self, pc: usize) -> bool {
+        self.breakpoints.remove(&pc)
+    }
+
+    /// Stops after an instruction changed `reg`.
+    pub fn watch(&mut self, reg: char) -> ExecutionResult<()> {
+        self.computer.registers.get(reg)?;
+        self.watches.insert(reg);
+        Ok(())
+    }
+
+    pub fn unwatch(&mut self, reg: char) -> bool {
+        self.watches.remove(&reg)
+    }
+
+    /// Starts recording the executed instructions, or stops and forgets them.
+    pub fn set_tracing(&mut self, enabled: bool) {
+        self.trace = enabled.then(Vec::new);
+    }
+
+    /// The instructions executed since tracing started, oldest first.
+    pub fn trace(&self) -> &[Step] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    /// Executes the current instruction, breakpoint or not.
+    pub fn step(&mut self) -> ExecutionResult<Event> {
+        if self.computer.current(self.program).is_none() {
+            return Ok(Event::Halted);
+        }
+        self.computer.check_loop(&mut self.seen)?;
+
+        let pc = self.computer.pc;
+        let before = self.computer.registers;
+        let Some(instruction) = self.computer.step(self.program)? else {
+            return Ok(Event::Halted);
+        };
+        self.at_breakpoint = false;
+        let after = self.computer.registers;
+        if let Some(trace) = &mut self.trace {
+            trace.push(Step {
+                pc,
+                instruction,
+                registers: after,
+            });
+        }
+
+        for &reg in &self.watches {
+            let (from, to) = (before.get(reg)?, after.get(reg)?);
+            if from != to {
+                return Ok(Event::Changed { reg, from, to });
+            }
+        }
+        Ok(Event::Stepped)
+    }
+
+    /// Executes instructions until a breakpoint, a watched register changing
+    /// or the program halting.
+    pub fn resume(&mut self) -> ExecutionResult<Event> {
+        loop {
+            let pc = self.computer.pc;
+            if self.breakpoints.contains(&pc) && !self.at_breakpoint {
+                self.at_breakpoint = true;
+                return Ok(Event::Breakpoint(pc));
+            }
+            match self.step()? {
+                Event::Stepped => {}
+                event => return Ok(event),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "inc a\njio a, +2\ntpl a\ninc a\n";
+
+    #[test]
+    fn test_parser() {
+        assert_eq!("hlf a".parse(), Ok(Instruction::HLF('a')));
+        assert_eq!("tpl a".parse(), Ok(Instruction::TPL('a')));
+        assert_eq!("inc a".parse(), Ok(Instruction::INC('a')));
+        assert_eq!("jmp -2".parse(), Ok(Instruction::JMP(-2)));
+        assert_eq!("jio a, +2".parse(), Ok(Instruction::JIO('a', 2)));
+        assert_eq!("jie a, -2".parse(), Ok(Instruction::JIE('a', -2)));
+
+        assert!("inc c".parse::<Instruction>().is_err());
+        assert!("jie a".parse::<Instruction>().is_err());
+        assert!("nop a".parse::<Instruction>().is_err());
+        assert_eq!(
+            assemble("inc a\n\njmp x\n"),
+            Err("line 3: bad offset \"x\" in \"jmp x\"".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let program = assemble(EXAMPLE).unwrap();
+        assert_eq!(disassemble(&program), EXAMPLE);
+        assert_eq!(Instruction::JIE('b', -7).to_string(), "jie b, -7");
+
+        let source = "hlf b\ntpl a\njmp -2\njie b, +0\n";
+        assert_eq!(disassemble(&assemble(source).unwrap()), source);
+    }
+
+    #[test]
+    fn test_run() {
+        let program = vec![
+            Instruction::INC('a'),
+            Instruction::JIO('a', 2),
+            Instruction::TPL('a'),
+            Instruction::INC('a'),
+        ];
+
+        let mut computer = Computer::new(0, 0);
+        let result = computer.run(&program);
+        assert_eq!(result, Ok((2, 0)));
+    }
+
+    #[test]
+    fn test_errors() {
+        let program = assemble("inc a\njmp -2").unwrap();
+        assert_eq!(
+            Computer::new(0, 0).run(&program),
+            Err(ExecutionError::InvalidJump {
+                pc: 1,
+                instruction: Instruction::JMP(-2)
+            })
+        );
+
+        let program = assemble("tpl a").unwrap();
+        assert!(matches!(
+            Computer::new(usize::MAX, 0).run(&program),
+            Err(ExecutionError::Overflow { pc: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_infinite_loop() {
+        // halves a until it is 1 then jumps back, forever
+        let program = assemble("jio a, +2\nhlf a\njmp -2").unwrap();
+        assert_eq!(
+            Computer::new(4, 0).run(&program),
+            Err(ExecutionError::InfiniteLoop {
+                pc: 2,
+                registers: Registers { a: 1, b: 0 }
+            })
+        );
+
+        let mut debugger = Debugger::new(&program, Computer::new(4, 0));
+        debugger.set_tracing(true);
+        assert!(debugger.resume().is_err());
+        let trace: Vec<_> = debugger
+            .trace()
+            .iter()
+            .map(|step| (step.pc, step.registers.a))
+            .collect();
+        assert_eq!(
+            trace,
+            vec![(0, 4), (1, 2), (2, 2), (0, 2), (1, 1), (2, 1), (0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_debugger() {
+        let program = assemble(EXAMPLE).unwrap();
+        let mut debugger = Debugger::new(&program, Computer::new(0, 0));
+        debugger.set_tracing(true);
+        debugger.add_breakpoint(0);
+        debugger.add_breakpoint(3);
+
+        assert_eq!(debugger.resume(), Ok(Event::Breakpoint(0)));
+        assert_eq!(debugger.step(), Ok(Event::Stepped));
+        assert_eq!(debugger.computer().registers(), Registers { a: 1, b: 0 });
+        assert_eq!(debugger.resume(), Ok(Event::Breakpoint(3)));
+        assert_eq!(debugger.resume(), Ok(Event::Halted));
+        assert_eq!(debugger.resume(), Ok(Event::Halted));
+
+        let trace: Vec<_> = debugger.trace().iter().map(|step| step.pc).collect();
+        assert_eq!(trace, vec![0, 1, 3]);
+        assert_eq!(
+            debugger.trace()[1].to_string(),
+            "   1: jio a, +2    a=1 b=0"
+        );
+        assert_eq!(
+            debugger.into_computer().registers(),
+            Registers { a: 2, b: 0 }
+        );
+    }
+
+    #[test]
+    fn test_watch() {
+        let program = assemble("inc b\ninc a\ntpl a\nhlf b\nhlf a").unwrap();
+        let mut debugger = Debugger::new(&program, Computer::new(0, 0));
+        assert_eq!(
+            debugger.watch('c'),
+            Err(ExecutionError::NoSuchRegister('c'))
+        );
+        debugger.watch('a').unwrap();
+
+        let changed = |from, to| Ok(Event::Changed { reg: 'a', from, to });
+        assert_eq!(debugger.resume(), changed(0, 1));
+        assert_eq!(debugger.resume(), changed(1, 3));
+        assert_eq!(debugger.resume(), changed(3, 1));
+        assert!(debugger.unwatch('a'));
+        assert_eq!(debugger.resume(), Ok(Event::Halted));
+        assert_eq!(debugger.computer().registers(), Registers { a: 1, b: 0 });
+    }
+}
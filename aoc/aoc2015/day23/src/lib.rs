@@ -0,0 +1,49 @@
+mod computer;
+
+use std::fmt::Display;
+
+pub use computer::{
+    assemble, disassemble, Computer, Debugger, Event, ExecutionError, ExecutionResult, Instruction,
+    Registers, Step,
+};
+use utils::solution::{Result, Solution};
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    /// Register b after running the program from a=0 and from a=1. A program
+    /// that fails to run has no answer, so it is run while parsing.
+    type Input = (usize, usize);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let program = assemble(input)?;
+        Ok((run(&program, 0)?, run(&program, 1)?))
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        input.0
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        input.1
+    }
+}
+
+/// Register b once the program has run from `reg_a`.
+fn run(program: &[Instruction], reg_a: usize) -> ExecutionResult<usize> {
+    let (_, reg_b) = Computer::new(reg_a, 0).run(program)?;
+    Ok(reg_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_programs_that_fail_are_rejected() {
+        assert_eq!(Day23::parse("inc b\ninc a").unwrap(), (1, 1));
+        assert_eq!(Day23::parse("jio a, +2\ninc b").unwrap(), (1, 0));
+        assert!(Day23::parse("jmp +0").is_err());
+        assert!(Day23::parse("jmp -1").is_err());
+    }
+}
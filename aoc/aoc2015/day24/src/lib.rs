@@ -1,19 +1,26 @@
-use std::{collections::HashSet, fs::File, io::Read};
+use std::{collections::HashSet, fmt::Display};
 
-fn main() {
-    let mut file = File::open("input.txt").expect("Failed to open input.txt");
-    let mut data = String::new();
-    file.read_to_string(&mut data)
-        .expect("Failed to read input");
+use utils::parse;
+use utils::solution::{Result, Solution};
 
-    let packages: Vec<usize> = data.lines().map(|l| l.parse().unwrap()).collect();
-    println!("{:?}", packages);
+pub struct Day24;
 
-    let (_, s) = calculate(&packages, 3);
-    println!("Optimal for [3] = {}", s);
+impl Solution for Day24 {
+    type Input = Vec<usize>;
 
-    let (_, s) = calculate(&packages, 4);
-    println!("Optimal for [4] = {}", s);
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::numbers(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        let (_, s) = calculate(input, 3);
+        s
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        let (_, s) = calculate(input, 4);
+        s
+    }
 }
 
 fn generate_variants(packages: &Vec<usize>, weight: usize, len: usize) -> Vec<Vec<usize>> {
@@ -0,0 +1,76 @@
+use std::fmt::Display;
+
+use utils::solution::{Result, Solution};
+
+const STEP: usize = 252533;
+const MOD: usize = 33554393;
+
+const FIRST: usize = 20151125;
+
+fn parse_position(input: &str) -> Result<(usize, usize)> {
+    // ... Enter the code at row 2978, column 3083.
+    let numbers: Vec<usize> = input
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse())
+        .collect::<std::result::Result<_, _>>()?;
+    match numbers[..] {
+        [row, col] => Ok((row, col)),
+        _ => Err(format!("Expected a row and a column in: {}", input.trim()).into()),
+    }
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = (usize, usize);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_position(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        let (row, col) = *input;
+        calc_next(FIRST, row, col)
+    }
+
+    fn part2(_: &Self::Input) -> impl Display {
+        // the last day has a single puzzle
+        "-"
+    }
+}
+
+fn calc_next(start: usize, row: usize, col: usize) -> usize {
+    let mut next = start;
+    let mut r = 1;
+    let mut c = 1;
+    loop {
+        if r == row && c == col {
+            break;
+        }
+        if r == 1 {
+            r = c + 1;
+            c = 1;
+        } else {
+            r -= 1;
+            c += 1;
+        }
+        next = (next * STEP) % MOD;
+    }
+
+    next
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        let start = 20151125;
+
+        assert_eq!(calc_next(start, 1, 1), start);
+        assert_eq!(calc_next(start, 2, 1), 31916031);
+        assert_eq!(calc_next(start, 1, 2), 18749137);
+    }
+}
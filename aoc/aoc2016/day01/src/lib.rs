@@ -1,10 +1,11 @@
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::Read;
+use std::fmt::Display;
 use std::str::FromStr;
 
+use utils::solution::{self, Solution};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum Step {
+pub enum Step {
     Left(isize),
     Right(isize),
 }
@@ -95,23 +96,28 @@ impl Position {
     }
 }
 
-fn main() {
-    let mut file = File::open("input.txt").expect("Failed to open input.txt");
-    let mut data = String::new();
-    file.read_to_string(&mut data).expect("Failed to read input");
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<Step>;
 
-    let mut steps = Vec::new();
+    fn parse(input: &str) -> solution::Result<Self::Input> {
+        let mut steps = Vec::new();
 
-    for part in data.split(", ") {
-        let step: Step = part.parse().unwrap();
-        steps.push(step);
+        for part in input.split(", ") {
+            let step: Step = part.parse()?;
+            steps.push(step);
+        }
+        Ok(steps)
     }
 
-    let position = find_hq(&steps, false);
-    println!("HQ distance = {}", position.distance());
+    fn part1(input: &Self::Input) -> impl Display {
+        find_hq(input, false).distance()
+    }
 
-    let position = find_hq(&steps, true);
-    println!("HQ Distance (1st visited) = {}", position.distance());
+    fn part2(input: &Self::Input) -> impl Display {
+        find_hq(input, true).distance()
+    }
 }
 
 fn find_hq(steps: &Vec<Step>, stop_on_visited: bool) -> Position {
@@ -1,5 +1,6 @@
-use std::fs::File;
-use std::io::prelude::*;
+use std::fmt::Display;
+
+use utils::solution::{self, Solution};
 
 trait Keyboard {
     fn generate_next(&self, instruction: &str, current: u32) -> Result<u32, &'static str>;
@@ -63,21 +64,24 @@ impl Keyboard for ExtendedKeyboard {
     }
 }
 
-fn main() {
-    let mut file = File::open("input.txt").expect("Failed to open input.txt");
+pub struct Day02;
 
-    let mut data = String::new();
-    file.read_to_string(&mut data)
-        .expect("Failed to read operations");
+impl Solution for Day02 {
+    type Input = String;
 
-    let keyboard = SimpleKeyboard {};
-    println!("bathroom code = {:?}", keyboard.find_code(&data));
+    fn parse(input: &str) -> solution::Result<Self::Input> {
+        Ok(input.to_owned())
+    }
 
-    let keyboard = ExtendedKeyboard {};
-    println!(
-        "bathroom code with extended keyboard = {:?}",
-        keyboard.find_code(&data)
-    );
+    fn part1(input: &Self::Input) -> impl Display {
+        let keyboard = SimpleKeyboard {};
+        keyboard.find_code(input).unwrap_or_else(str::to_owned)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        let keyboard = ExtendedKeyboard {};
+        keyboard.find_code(input).unwrap_or_else(str::to_owned)
+    }
 }
 
 #[cfg(test)]
@@ -1,7 +1,8 @@
-use std::fs::File;
-use std::io::prelude::*;
+use std::fmt::Display;
 use std::str::FromStr;
 
+use utils::solution::{self, Solution};
+
 #[derive(Debug, PartialEq, Eq)]
 struct Triangle(u32, u32, u32);
 
@@ -23,14 +24,22 @@ impl FromStr for Triangle {
     }
 }
 
-fn main() {
-    let mut file = File::open("input.txt").expect("Failed to open input.txt");
-    let mut data = String::new();
-    file.read_to_string(&mut data)
-        .expect("Failed to read input");
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = String;
+
+    fn parse(input: &str) -> solution::Result<Self::Input> {
+        Ok(input.to_owned())
+    }
 
-    println!("Number of Triangles (row) = {}", count_horizontal(&data));
-    println!("Number of Triangles (column) = {}", count_vertical(&data));
+    fn part1(input: &Self::Input) -> impl Display {
+        count_horizontal(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        count_vertical(input)
+    }
 }
 
 fn count_horizontal(data: &str) -> u32 {
@@ -1,10 +1,11 @@
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use std::fmt::Display;
 use std::str::FromStr;
 
+use utils::solution::{self, Solution};
+
 #[derive(Debug, PartialEq, Eq)]
-struct Room {
+pub struct Room {
     id: u32,
     checksum: String,
     encrypted: String,
@@ -70,31 +71,33 @@ impl Room {
     }
 }
 
-fn main() {
-    let file = File::open("input.txt").expect("failed to open input.txt");
-    let buffer = BufReader::new(file);
-
-    let mut rooms: Vec<Room> = Vec::new();
-    let mut sector_sum = 0;
-    for line in buffer.lines() {
-        let line = line.expect("Failed to read lint");
-        if let Ok(room) = line.parse::<Room>() {
-            if room.is_valid() {
-                sector_sum += room.id;
-                rooms.push(room);
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Room>;
+
+    fn parse(input: &str) -> solution::Result<Self::Input> {
+        let mut rooms: Vec<Room> = Vec::new();
+        for line in input.lines() {
+            if let Ok(room) = line.parse::<Room>() {
+                if room.is_valid() {
+                    rooms.push(room);
+                }
             }
         }
+        Ok(rooms)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        input.iter().map(|room| room.id).sum::<u32>()
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        input
+            .iter()
+            .find(|room| room.decrypt().contains("northpole"))
+            .map_or(0, |room| room.id)
     }
-    println!("Sum of sectorID's = {}", sector_sum);
-
-    let northpole_room = rooms
-        .iter()
-        .find(|room| room.decrypt().contains("northpole"))
-        .unwrap();
-    println!(
-        "SectorID of 'northpole object storage': {}",
-        northpole_room.id
-    );
 }
 
 #[cfg(test)]
@@ -1,19 +1,27 @@
-fn main() {
-    let input = "wtnhxymk";
+use std::fmt::Display;
 
-    let password = first_door_password(input);
-    println!("The password for 1st door = {}", password);
+use utils::solution::{Result, Solution};
 
-    let second_password = second_door_password(input);
-    println!("The password for 2nd door = {}", second_password);
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.trim().to_owned())
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        first_door_password(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        second_door_password(input)
+    }
 }
 
 fn first_door_password(door_id: &str) -> String {
     let mut password: [u8; 8] = [b'-'; 8];
-    println!(
-        "password = '{}'",
-        password.iter().map(|b| *b as char).collect::<String>()
-    );
 
     let mut offset = 0;
 
@@ -22,20 +30,12 @@ fn first_door_password(door_id: &str) -> String {
         offset = new_offset + 1;
         let char = hash.as_bytes()[5];
         password[i] = char;
-        println!(
-            "password = '{}'",
-            password.iter().map(|b| *b as char).collect::<String>()
-        );
     }
     password.iter().map(|b| *b as char).collect()
 }
 
 fn second_door_password(door_id: &str) -> String {
     let mut password: [u8; 8] = [b'-'; 8];
-    println!(
-        "password = '{}'",
-        password.iter().map(|b| *b as char).collect::<String>()
-    );
     let mut offset = 0;
     for _ in 0..8 {
         loop {
@@ -45,10 +45,6 @@ fn second_door_password(door_id: &str) -> String {
             let char = hash.as_bytes()[6];
             if position < 8 && password[position] == b'-' {
                 password[position] = char;
-                println!(
-                    "password = '{}'",
-                    password.iter().map(|b| *b as char).collect::<String>()
-                );
                 break;
             }
         }
@@ -1,14 +1,23 @@
-use std::{collections::HashMap, fs::File, io::Read};
+use std::{collections::HashMap, fmt::Display};
 
-fn main() {
-    let mut file = File::open("input.txt").expect("Failed to open input.txt");
-    let mut data = String::new();
-    file.read_to_string(&mut data).expect("Failed to read data");
+use utils::solution::{Result, Solution};
 
-    let message = recover_most_occurrence(&data);
-    println!("recovered with most occurrences = {}", message);
-    let message = recover_least_occurrence(&data);
-    println!("recovered with least occurrences = {}", message);
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        recover_most_occurrence(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        recover_least_occurrence(input)
+    }
 }
 
 fn count_occurrences(data: &str) -> Vec<HashMap<char, usize>> {
@@ -55,6 +64,7 @@ fn recover_least_occurrence(data: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::input::Example;
 
     #[test]
     fn test_message_recovery() {
@@ -78,4 +88,29 @@ mod tests {
         assert_eq!(recover_most_occurrence(data), "easter".to_owned());
         assert_eq!(recover_least_occurrence(data), "advent".to_owned());
     }
+
+    #[test]
+    fn test_example() {
+        Example::new(
+            "eedadn
+            drvtee
+            eandsr
+            raavrd
+            atevrs
+            tsrnev
+            sdttsa
+            rasrtv
+            nssdts
+            ntnada
+            svetve
+            tesnvt
+            vntsnd
+            vrdear
+            dvrsen
+            enarar",
+        )
+        .part1("easter")
+        .part2("advent")
+        .check::<Day06>();
+    }
 }
@@ -1,8 +1,9 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fmt::Display;
+
+use utils::solution::{Result, Solution};
 
 #[derive(Debug)]
-struct IPv7(String);
+pub struct IPv7(String);
 
 impl IPv7 {
     fn new(s: &str) -> Self {
@@ -113,25 +114,22 @@ impl IPv7 {
     }
 }
 
-fn main() {
-    let file = File::open("input.txt").unwrap();
-    let buffer = BufReader::new(file);
+pub struct Day07;
 
-    let mut tls_count = 0;
-    let mut ssl_count = 0;
+impl Solution for Day07 {
+    type Input = Vec<IPv7>;
 
-    for line in buffer.lines() {
-        let ip = IPv7::new(&line.unwrap());
-        if ip.is_tls() {
-            tls_count += 1;
-        }
-        if ip.is_ssl() {
-            ssl_count += 1;
-        }
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(IPv7::new).collect())
     }
 
-    println!("tls count = {}", tls_count);
-    println!("ssl count = {}", ssl_count);
+    fn part1(input: &Self::Input) -> impl Display {
+        input.iter().filter(|ip| ip.is_tls()).count()
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        input.iter().filter(|ip| ip.is_ssl()).count()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,173 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use utils::grid::Grid;
+use utils::solution::{self, Solution};
+
+const WIDTH: usize = 50;
+const HEIGHT: usize = 6;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Rect(usize, usize),
+    RotateRow(usize, usize),
+    RotateCol(usize, usize),
+}
+
+impl FromStr for Command {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut split = s.split(' ');
+        let cmd = split.next().ok_or("invalid command")?;
+        match cmd {
+            "rect" => {
+                // rect AxB
+                let dimensions = split.next().ok_or("invalid command")?;
+                let mut split = dimensions.split('x');
+                let col: usize = split.next().and_then(|c| c.parse().ok()).ok_or("invalid command")?;
+                let row: usize = split.next().and_then(|r| r.parse().ok()).ok_or("invalid command")?;
+                Ok(Command::Rect(col, row))
+            }
+            "rotate" => {
+                // rotate row y=A by B
+                // rotate column x=A by B
+                let direction = split.next().ok_or("invalid command")?.trim();
+                let index: usize = split
+                    .next()
+                    .and_then(|i| i.replace("y=", "").replace("x=", "").parse().ok())
+                    .ok_or("invalid command")?;
+                let count: usize = split.nth(1).and_then(|c| c.parse().ok()).ok_or("invalid command")?;
+                match direction {
+                    "row" => Ok(Command::RotateRow(index, count)),
+                    "column" => Ok(Command::RotateCol(index, count)),
+                    _ => Err("invalid command"),
+                }
+            }
+            _ => Err("invalid command"),
+        }
+    }
+}
+
+impl Command {
+    /// Checks that a rotation names a row or column of a `width` by `height`
+    /// screen. Rectangles are cut to the screen instead.
+    fn check(&self, width: usize, height: usize) -> Result<(), String> {
+        match *self {
+            Command::RotateRow(y, _) if y >= height => Err(format!("row {} is off screen", y)),
+            Command::RotateCol(x, _) if x >= width => Err(format!("column {} is off screen", x)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Renders the screen the way the code is read off it.
+fn render(screen: &Grid<bool>) -> String {
+    let mut result = String::new();
+    for row in screen.rows() {
+        for pixel in row {
+            result += if *pixel { "* " } else { "_ " };
+        }
+        result += "\n";
+    }
+    result + "\n"
+}
+
+fn show(screen: &mut Grid<bool>, commands: &[Command]) {
+    for command in commands {
+        match *command {
+            Command::Rect(c, r) => {
+                let c = c.min(screen.width());
+                let r = r.min(screen.height());
+                screen.fill_rect(0..c, 0..r, true);
+            }
+            Command::RotateRow(row_idx, count) => screen.rotate_row(row_idx, count),
+            Command::RotateCol(col_idx, count) => screen.rotate_column(col_idx, count),
+        }
+    }
+}
+
+fn draw(commands: &[Command]) -> Grid<bool> {
+    let mut screen = Grid::new(WIDTH, HEIGHT, false);
+    show(&mut screen, commands);
+    screen
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Vec<Command>;
+
+    fn parse(input: &str) -> solution::Result<Self::Input> {
+        let mut commands = Vec::new();
+
+        for line in input.lines() {
+            let cmd: Command = line.parse()?;
+            cmd.check(WIDTH, HEIGHT)?;
+            commands.push(cmd);
+        }
+        Ok(commands)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        draw(input).count(|pixel| *pixel)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        // the code is read off the lit pixels
+        format!("\n{}", render(&draw(input)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser() {
+        assert_eq!("rect 3x2".parse(), Ok(Command::Rect(3, 2)));
+        assert_eq!("rotate column x=1 by 1".parse(), Ok(Command::RotateCol(1, 1)));
+        assert_eq!("rotate row y=0 by 4".parse(), Ok(Command::RotateRow(0, 4)));
+    }
+
+    #[test]
+    fn test_out_of_screen() {
+        assert!(Day08::parse("rotate row y=5 by 1").is_ok());
+        assert!(Day08::parse("rotate row y=6 by 1").is_err());
+        assert!(Day08::parse("rotate column x=50 by 1").is_err());
+        assert!(Day08::parse("rect 60x10").is_ok());
+    }
+
+    #[test]
+    fn test_rect() {
+        let mut screen = Grid::new(7, 3, false);
+        show(&mut screen, &[Command::Rect(3, 2)]);
+        println!("{}", screen);
+        assert_eq!(screen.count(|pixel| *pixel), 6);
+        assert!(screen[(0, 0)]);
+        assert!(screen[(0, 1)]);
+        assert!(screen[(1, 0)]);
+        assert!(screen[(1, 1)]);
+        assert!(screen[(2, 0)]);
+        assert!(screen[(2, 1)]);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut screen = Grid::new(7, 3, false);
+        show(&mut screen, &[Command::Rect(3, 2), Command::RotateCol(1, 1)]);
+        println!("{}", screen);
+        assert!(!screen[(1, 0)]);
+        assert!(screen[(1, 2)]);
+        show(&mut screen, &[Command::RotateRow(0, 4)]);
+        println!("{}", screen);
+        assert!(screen[(4, 0)]);
+        assert!(screen[(6, 0)]);
+        assert!(!screen[(0, 0)]);
+        assert!(!screen[(2, 0)]);
+        show(&mut screen, &[Command::RotateCol(1, 1)]);
+        println!("{}", screen);
+        assert!(screen[(1, 0)]);
+        assert!(!screen[(1, 1)]);
+    }
+}
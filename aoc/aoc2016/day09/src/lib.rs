@@ -1,20 +1,28 @@
-use std::{fs::File, io::Read};
+use std::fmt::Display;
 
-fn main() {
-    let mut file = File::open("input.txt").unwrap();
-    let mut data = String::new();
-    file.read_to_string(&mut data).unwrap();
+use utils::solution::{Result, Solution};
 
-    let extracted = extract(&data);
-    println!("Extracted length = {}", extracted.len());
+pub struct Day09;
 
-    let extracted = extended_count(&data);
-    println!("Extended extract length = {}", extracted);
+impl Solution for Day09 {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        extract(input).len()
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        extended_count(input)
+    }
 }
 
 fn extract(data: &str) -> String {
     let mut result = String::new();
-    let mut input = data.clone().trim();
+    let mut input = data.trim();
 
     while let Some(start) = input.find('(') {
         result += input.get(..start).unwrap();
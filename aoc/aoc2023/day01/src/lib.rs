@@ -2,8 +2,9 @@
 extern crate lazy_static;
 
 use std::collections::HashMap;
+use std::fmt::Display;
 
-use utils::files::read_lines;
+use utils::solution::{Result, Solution};
 
 fn process_line_part1(line: &str) -> u32 {
     let mut val = 0;
@@ -72,18 +73,27 @@ fn process_line_part2(line: &str) -> u32 {
     return first * 10 + second;
 }
 
-fn main() {
-    let mut part1 = 0;
-    let mut part2 = 0;
-    if let Ok(lines) = read_lines("day01/input.txt") {
-        for line in lines.flatten() {
-            part1 += process_line_part1(&line);
-            part2 += process_line_part2(&line);
-        }
-        println!("Part 1 = {part1}");
-        println!("Part 2 = {part2}");
-    } else {
-        println!("input not found!");
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(str::to_owned).collect())
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        input
+            .iter()
+            .map(|line| process_line_part1(line))
+            .sum::<u32>()
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        input
+            .iter()
+            .map(|line| process_line_part2(line))
+            .sum::<u32>()
     }
 }
 
@@ -125,4 +135,30 @@ mod day01_tests {
             assert_eq!(result, *v);
         }
     }
+    #[test]
+    fn test_examples() {
+        use super::*;
+        use utils::input::Example;
+
+        Example::new(
+            "1abc2
+            pqr3stu8vwx
+            a1b2c3d4e5f
+            treb7uchet",
+        )
+        .part1(142)
+        .check::<Day01>();
+
+        Example::new(
+            "two1nine
+            eightwothree
+            abcone2threexyz
+            xtwone3four
+            4nineeightseven2
+            zoneight234
+            7pqrstsixteen",
+        )
+        .part2(281)
+        .check::<Day01>();
+    }
 }
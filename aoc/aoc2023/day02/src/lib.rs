@@ -0,0 +1,36 @@
+use std::fmt::Display;
+
+use utils::parse;
+use utils::solution::{Result, Solution};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::lines(input)?)
+    }
+
+    fn part1(_input: &Self::Input) -> impl Display {
+        0
+    }
+
+    fn part2(_input: &Self::Input) -> impl Display {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::input::Example;
+
+    #[test]
+    #[ignore = "the example is not filled in yet"]
+    fn test_example() {
+        // TODO: the example of the puzzle text and the answers it is given,
+        // then drop the `#[ignore]`.
+        Example::new("").part1(0).part2(0).check::<Day02>();
+    }
+}
@@ -0,0 +1,149 @@
+//! Timing solutions over repeated runs.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::solution::{Day, Result};
+
+/// The spread of a step's timings over all runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// `None` without any samples.
+    pub fn new(mut samples: Vec<Duration>) -> Option<Self> {
+        samples.sort();
+        let len = samples.len();
+        let median = match len {
+            0 => return None,
+            _ if len % 2 == 1 => samples[len / 2],
+            _ => (samples[len / 2 - 1] + samples[len / 2]) / 2,
+        };
+        Some(Self {
+            min: samples[0],
+            median,
+            max: samples[len - 1],
+        })
+    }
+}
+
+/// The timings of a day's parsing and parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Report {
+    pub year: u16,
+    pub day: u8,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+/// Solves `day` with `input` `runs` times, which must be at least 1.
+pub fn bench(day: &Day, input: &str, runs: usize) -> Result<Report> {
+    let mut parse = Vec::with_capacity(runs);
+    let mut part1 = Vec::with_capacity(runs);
+    let mut part2 = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let (_, timings) = day.solve_timed(input)?;
+        parse.push(timings.parse);
+        part1.push(timings.part1);
+        part2.push(timings.part2);
+    }
+    let stats = |samples| Stats::new(samples).ok_or("at least one run is required");
+    Ok(Report {
+        year: day.year,
+        day: day.day,
+        parse: stats(parse)?,
+        part1: stats(part1)?,
+        part2: stats(part2)?,
+    })
+}
+
+fn steps(report: &Report) -> [(&'static str, &Stats); 3] {
+    [
+        ("parse", &report.parse),
+        ("part 1", &report.part1),
+        ("part 2", &report.part2),
+    ]
+}
+
+/// One line per step of every report.
+pub fn text(reports: &[Report]) -> String {
+    let mut result = String::new();
+    for report in reports {
+        for (idx, (step, stats)) in steps(report).into_iter().enumerate() {
+            let title = match idx {
+                0 => format!("{} day {:02}", report.year, report.day),
+                _ => String::new(),
+            };
+            writeln!(
+                result,
+                "{:<12} {:<6}  min {:>10.2?}  median {:>10.2?}  max {:>10.2?}",
+                title, step, stats.min, stats.median, stats.max
+            )
+            .unwrap();
+        }
+    }
+    result
+}
+
+/// A table with the median and range of every step, for the README.
+pub fn markdown(reports: &[Report]) -> String {
+    let mut result = String::from("| Year | Day | Parse | Part 1 | Part 2 |\n");
+    result += "|------|-----|-------|--------|--------|\n";
+    for report in reports {
+        write!(result, "| {} | {} |", report.year, report.day).unwrap();
+        for (_, stats) in steps(report) {
+            write!(
+                result,
+                " {:.2?} ({:.2?} – {:.2?}) |",
+                stats.median, stats.min, stats.max
+            )
+            .unwrap();
+        }
+        result.push('\n');
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(Stats::new(vec![]), None);
+
+        let stats = Stats::new(vec![ms(5), ms(1), ms(3)]).unwrap();
+        assert_eq!((stats.min, stats.median, stats.max), (ms(1), ms(3), ms(5)));
+
+        let stats = Stats::new(vec![ms(4), ms(1), ms(2), ms(8)]).unwrap();
+        assert_eq!((stats.min, stats.median, stats.max), (ms(1), ms(3), ms(8)));
+    }
+
+    #[test]
+    fn test_markdown() {
+        let stats = Stats::new(vec![ms(1), ms(2), ms(4)]).unwrap();
+        let report = Report {
+            year: 2015,
+            day: 7,
+            parse: stats,
+            part1: stats,
+            part2: stats,
+        };
+        let table = markdown(&[report]);
+        let rows: Vec<_> = table.lines().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[2],
+            "| 2015 | 7 | 2.00ms (1.00ms – 4.00ms) | 2.00ms (1.00ms – 4.00ms) | 2.00ms (1.00ms – 4.00ms) |"
+        );
+    }
+}
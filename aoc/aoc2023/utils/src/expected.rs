@@ -0,0 +1,214 @@
+//! The answers of solved days, recorded so that a change to shared code which
+//! breaks an old puzzle does not go unnoticed.
+//!
+//! The store is a text file with one `year day part answer` line per part.
+//! Newlines and backslashes in answers are escaped as `\n` and `\\`, and
+//! lines starting with `#` are comments.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::parse::{ParseError, Result};
+use crate::solution::Answers;
+
+/// A part whose answer is not the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difference {
+    pub part: u8,
+    /// `None` if no answer was recorded for the part.
+    pub expected: Option<String>,
+    pub actual: String,
+}
+
+impl Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.expected {
+            Some(expected) => write!(
+                f,
+                "part {} changed from {:?} to {:?}",
+                self.part, expected, self.actual
+            ),
+            None => write!(
+                f,
+                "part {} has no recorded answer, got {:?}",
+                self.part, self.actual
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Expected {
+    answers: BTreeMap<(u16, u8, u8), String>,
+}
+
+fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(answer: &str) -> String {
+    let mut result = String::new();
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                result.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                result.push('\\');
+                chars.next();
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// Parses the next of `fields`, which starts at `column`, as a number in
+/// `range` and moves `column` past it. Fails with the field's column.
+fn field<'a, T>(
+    fields: &mut impl Iterator<Item = &'a str>,
+    column: &mut usize,
+    range: RangeInclusive<T>,
+) -> std::result::Result<T, usize>
+where
+    T: FromStr + PartialOrd,
+{
+    let field = fields.next().unwrap_or_default();
+    let start = *column;
+    *column += field.len() + 1;
+    match field.parse() {
+        Ok(value) if range.contains(&value) => Ok(value),
+        _ => Err(start),
+    }
+}
+
+impl Expected {
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut answers = BTreeMap::new();
+        for (idx, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |column| {
+                ParseError::new(
+                    idx + 1,
+                    column,
+                    format!(
+                        "expected `year day part answer` with a day from 1 to 25 and a part of 1 or 2, got {:?}",
+                        line
+                    ),
+                )
+            };
+            let mut fields = line.splitn(4, ' ');
+            let mut column = 1;
+            let year = field(&mut fields, &mut column, 0..=u16::MAX).map_err(error)?;
+            let day = field(&mut fields, &mut column, 1..=25).map_err(error)?;
+            let part = field(&mut fields, &mut column, 1..=2).map_err(error)?;
+            let answer = unescape(fields.next().unwrap_or_default());
+            answers.insert((year, day, part), answer);
+        }
+        Ok(Self { answers })
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(year, day, part)).map(String::as_str)
+    }
+
+    /// Records the answers of a day, replacing the previous ones.
+    pub fn record(&mut self, year: u16, day: u8, answers: &Answers) {
+        self.answers.insert((year, day, 1), answers.part1.clone());
+        self.answers.insert((year, day, 2), answers.part2.clone());
+    }
+
+    /// The parts of a day whose answers differ from the recorded ones.
+    pub fn verify(&self, year: u16, day: u8, answers: &Answers) -> Vec<Difference> {
+        [(1, &answers.part1), (2, &answers.part2)]
+            .into_iter()
+            .filter_map(|(part, actual)| {
+                let expected = self.get(year, day, part);
+                (expected != Some(actual.as_str())).then(|| Difference {
+                    part,
+                    expected: expected.map(str::to_owned),
+                    actual: actual.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# year day part answer")?;
+        for ((year, day, part), answer) in &self.answers {
+            writeln!(f, "{} {} {} {}", year, day, part, escape(answer))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(part1: &str, part2: &str) -> Answers {
+        Answers {
+            part1: part1.to_owned(),
+            part2: part2.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut expected = Expected::default();
+        expected.record(2016, 8, &answers("119", "\n* _\n_ *\\\n"));
+        expected.record(2015, 1, &answers("280", "1797"));
+
+        let text = expected.to_string();
+        assert!(text.contains("2015 1 1 280\n"));
+        assert!(text.contains("2016 8 2 \\n* _\\n_ *\\\\\\n\n"));
+        assert_eq!(Expected::parse(&text), Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Expected::parse("# comment\n2015 1 1 280\n2015 x 2 1797").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 6));
+
+        let err = Expected::parse("2015 26 1 280").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 6));
+        let err = Expected::parse("2015 1 3 280").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 8));
+        let err = Expected::parse("2015 257 1 280").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 6));
+        let err = Expected::parse("2015 0 1 280").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 6));
+    }
+
+    #[test]
+    fn test_verify() {
+        let mut expected = Expected::default();
+        expected.record(2015, 7, &answers("46065", "14134"));
+
+        assert!(expected
+            .verify(2015, 7, &answers("46065", "14134"))
+            .is_empty());
+
+        let differences = expected.verify(2015, 7, &answers("46065", "1"));
+        assert_eq!(
+            differences,
+            vec![Difference {
+                part: 2,
+                expected: Some("14134".to_owned()),
+                actual: "1".to_owned(),
+            }]
+        );
+
+        let differences = expected.verify(2015, 8, &answers("1", "2"));
+        assert_eq!(differences.len(), 2);
+        assert_eq!(differences[0].expected, None);
+    }
+}
@@ -0,0 +1,434 @@
+//! Weighted graphs and the searches puzzles keep needing. Nodes are added
+//! with a label, e.g. a city name, and are then referred to by the index
+//! [`Graph::add_node`] returns.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::hash::Hash;
+
+/// The most nodes [`Graph::held_karp`] takes: its tables have `2^n * n`
+/// entries, about 200 MB for 20 nodes.
+pub const MAX_TOUR_NODES: usize = 20;
+
+/// Estimates the cost from a node to the goal of an [`Graph::astar`] search.
+/// The estimate must never exceed the actual cost for the path found to be
+/// the shortest.
+pub trait Heuristic {
+    fn estimate(&self, node: usize) -> i64;
+}
+
+impl<F: Fn(usize) -> i64> Heuristic for F {
+    fn estimate(&self, node: usize) -> i64 {
+        self(node)
+    }
+}
+
+/// Whether a search looks for the cheapest or the most expensive route.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    Min,
+    Max,
+}
+
+/// Whether a route visiting every node comes back to where it started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tour {
+    Path,
+    Cycle,
+}
+
+/// A route through a graph and its total weight. The nodes of a cycle do not
+/// repeat the first one at the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub cost: i64,
+    pub nodes: Vec<usize>,
+}
+
+/// Why [`Graph::held_karp`] found no route.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TourError {
+    /// The graph has more than [`MAX_TOUR_NODES`] nodes.
+    TooManyNodes(usize),
+    /// The graph is empty, or its edges allow no route visiting every node
+    /// once.
+    NoRoute,
+}
+
+impl Display for TourError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TourError::TooManyNodes(n) => write!(
+                f,
+                "{} nodes are too many to visit, at most {} are supported",
+                n, MAX_TOUR_NODES
+            ),
+            TourError::NoRoute => write!(f, "no route visits every node once"),
+        }
+    }
+}
+
+impl Error for TourError {}
+
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    labels: Vec<N>,
+    ids: HashMap<N, usize>,
+    edges: Vec<Vec<(usize, i64)>>,
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Self {
+        Self {
+            labels: Vec::new(),
+            ids: HashMap::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The index of the node labelled `label`, added if it is new.
+    pub fn add_node(&mut self, label: N) -> usize {
+        if let Some(&id) = self.ids.get(&label) {
+            return id;
+        }
+        let id = self.labels.len();
+        self.labels.push(label.clone());
+        self.ids.insert(label, id);
+        self.edges.push(Vec::new());
+        id
+    }
+
+    /// Adds an edge from `from` to `to`, replacing any previous one.
+    pub fn add_edge(&mut self, from: N, to: N, weight: i64) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        let edges = &mut self.edges[from];
+        match edges.iter_mut().find(|(node, _)| *node == to) {
+            Some(edge) => edge.1 = weight,
+            None => edges.push((to, weight)),
+        }
+    }
+
+    /// Adds an edge both ways.
+    pub fn add_undirected_edge(&mut self, a: N, b: N, weight: i64) {
+        self.add_edge(a.clone(), b.clone(), weight);
+        self.add_edge(b, a, weight);
+    }
+
+    pub fn id(&self, label: &N) -> Option<usize> {
+        self.ids.get(label).copied()
+    }
+}
+
+impl<N> Graph<N> {
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn label(&self, node: usize) -> &N {
+        &self.labels[node]
+    }
+
+    /// The nodes `node` has an edge to, with the weights of the edges.
+    pub fn neighbors(&self, node: usize) -> &[(usize, i64)] {
+        &self.edges[node]
+    }
+
+    pub fn weight(&self, from: usize, to: usize) -> Option<i64> {
+        self.edges[from]
+            .iter()
+            .find(|(node, _)| *node == to)
+            .map(|(_, weight)| *weight)
+    }
+
+    /// The number of edges from `start` to every node, `None` for the nodes
+    /// it cannot reach. Weights are ignored.
+    pub fn bfs(&self, start: usize) -> Vec<Option<usize>> {
+        let mut steps = vec![None; self.len()];
+        steps[start] = Some(0);
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            let next = steps[node].map(|s| s + 1);
+            for &(neighbor, _) in self.neighbors(node) {
+                if steps[neighbor].is_none() {
+                    steps[neighbor] = next;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        steps
+    }
+
+    /// The cost of the cheapest path from `start` to every node, `None` for
+    /// the nodes it cannot reach. Weights must not be negative.
+    pub fn dijkstra(&self, start: usize) -> Vec<Option<i64>> {
+        let mut costs = vec![None; self.len()];
+        let mut queue = BinaryHeap::from([Reverse((0, start))]);
+        while let Some(Reverse((cost, node))) = queue.pop() {
+            if costs[node].is_some() {
+                continue;
+            }
+            costs[node] = Some(cost);
+            for &(neighbor, weight) in self.neighbors(node) {
+                if costs[neighbor].is_none() {
+                    queue.push(Reverse((cost + weight, neighbor)));
+                }
+            }
+        }
+        costs
+    }
+
+    /// The cheapest path from `start` to `goal`, guided by `heuristic`.
+    /// Weights must not be negative.
+    pub fn astar(&self, start: usize, goal: usize, heuristic: &impl Heuristic) -> Option<Route> {
+        let mut costs: Vec<Option<i64>> = vec![None; self.len()];
+        let mut previous = vec![None; self.len()];
+        let mut done = vec![false; self.len()];
+        costs[start] = Some(0);
+        let mut queue = BinaryHeap::from([Reverse((heuristic.estimate(start), start))]);
+
+        while let Some(Reverse((_, node))) = queue.pop() {
+            if node == goal {
+                let mut nodes = vec![goal];
+                while let Some(prev) = previous[*nodes.last().unwrap()] {
+                    nodes.push(prev);
+                }
+                nodes.reverse();
+                return costs[goal].map(|cost| Route { cost, nodes });
+            }
+            if std::mem::replace(&mut done[node], true) {
+                continue;
+            }
+            let cost = costs[node].expect("queued nodes have a cost");
+            for &(neighbor, weight) in self.neighbors(node) {
+                let next = cost + weight;
+                if costs[neighbor].is_none_or(|c| next < c) {
+                    costs[neighbor] = Some(next);
+                    previous[neighbor] = Some(node);
+                    queue.push(Reverse((next + heuristic.estimate(neighbor), neighbor)));
+                }
+            }
+        }
+        None
+    }
+
+    /// Like [`Graph::astar`] without a heuristic.
+    pub fn shortest_path(&self, start: usize, goal: usize) -> Option<Route> {
+        self.astar(start, goal, &|_| 0)
+    }
+
+    /// The best route visiting every node once, found with the Held-Karp
+    /// dynamic programming over the subsets of visited nodes. A path may start
+    /// anywhere, a cycle starts at node 0. Takes `2^n * n^2` steps and `2^n * n`
+    /// table entries, so it fails for more than [`MAX_TOUR_NODES`] nodes.
+    pub fn held_karp(&self, objective: Objective, tour: Tour) -> Result<Route, TourError> {
+        let n = self.len();
+        if n > MAX_TOUR_NODES {
+            return Err(TourError::TooManyNodes(n));
+        }
+        if n == 0 {
+            return Err(TourError::NoRoute);
+        }
+        // routes not found yet are as bad as a route can be
+        let (unreachable, better): (i64, fn(i64, i64) -> bool) = match objective {
+            Objective::Min => (i64::MAX, |a, b| a < b),
+            Objective::Max => (i64::MIN, |a, b| a > b),
+        };
+
+        // best[set * n + last]: the best route visiting the nodes of `set`,
+        // ending at `last`, with the node before `last` in `previous`
+        let mut best = vec![unreachable; (1 << n) * n];
+        let mut previous = vec![0u8; (1 << n) * n];
+        match tour {
+            Tour::Path => (0..n).for_each(|node| best[(1 << node) * n + node] = 0),
+            Tour::Cycle => best[n] = 0,
+        }
+
+        for set in 1..1usize << n {
+            for last in 0..n {
+                let cost = best[set * n + last];
+                if cost == unreachable {
+                    continue;
+                }
+                for &(next, weight) in self.neighbors(last) {
+                    if set & (1 << next) != 0 {
+                        continue;
+                    }
+                    let entry = (set | (1 << next)) * n + next;
+                    let next_cost = cost + weight;
+                    if better(next_cost, best[entry]) {
+                        best[entry] = next_cost;
+                        previous[entry] = last as u8;
+                    }
+                }
+            }
+        }
+
+        let all = (1 << n) - 1;
+        let closing = |last: usize| match tour {
+            Tour::Path => Some(0),
+            Tour::Cycle if n == 1 => Some(0),
+            Tour::Cycle => self.weight(last, 0),
+        };
+        let (cost, mut last) = (0..n)
+            .filter(|&last| best[all * n + last] != unreachable)
+            .filter_map(|last| Some((best[all * n + last] + closing(last)?, last)))
+            .reduce(|a, b| if better(b.0, a.0) { b } else { a })
+            .ok_or(TourError::NoRoute)?;
+
+        let mut nodes = Vec::with_capacity(n);
+        let mut set = all;
+        while set != 0 {
+            nodes.push(last);
+            let prev = previous[set * n + last] as usize;
+            set &= !(1 << last);
+            last = prev;
+        }
+        nodes.reverse();
+        Ok(Route { cost, nodes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example of 2015 day 9 with a fourth city.
+    fn cities() -> Graph<&'static str> {
+        let mut graph = Graph::new();
+        for (a, b, distance) in [
+            ("London", "Dublin", 464),
+            ("London", "Belfast", 518),
+            ("Dublin", "Belfast", 141),
+            ("London", "Paris", 300),
+            ("Dublin", "Paris", 500),
+            ("Belfast", "Paris", 100),
+        ] {
+            graph.add_undirected_edge(a, b, distance);
+        }
+        graph
+    }
+
+    fn labels<'a>(graph: &Graph<&'a str>, route: &Route) -> Vec<&'a str> {
+        route.nodes.iter().map(|&node| *graph.label(node)).collect()
+    }
+
+    #[test]
+    fn test_nodes() {
+        let mut graph = cities();
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.add_node("Dublin"), 1);
+        assert_eq!(graph.id(&"Paris"), Some(3));
+        assert_eq!(graph.weight(2, 1), Some(141));
+
+        graph.add_edge("Paris", "Rome", 1400);
+        assert_eq!(graph.weight(3, 4), Some(1400));
+        assert_eq!(graph.weight(4, 3), None);
+    }
+
+    #[test]
+    fn test_bfs() {
+        let mut graph = Graph::new();
+        graph.add_edge('a', 'b', 5);
+        graph.add_edge('b', 'c', 5);
+        graph.add_edge('a', 'c', 50);
+        graph.add_node('d');
+        assert_eq!(graph.bfs(0), vec![Some(0), Some(1), Some(1), None]);
+    }
+
+    #[test]
+    fn test_shortest_paths() {
+        let graph = cities();
+        let london = graph.id(&"London").unwrap();
+        let dublin = graph.id(&"Dublin").unwrap();
+        assert_eq!(
+            graph.dijkstra(london),
+            vec![Some(0), Some(464), Some(400), Some(300)]
+        );
+
+        let route = graph.shortest_path(london, dublin).unwrap();
+        assert_eq!(route.cost, 464);
+
+        // a heuristic that never overestimates keeps the route optimal
+        let route = graph
+            .astar(dublin, london, &|node| if node == london { 0 } else { 100 })
+            .unwrap();
+        assert_eq!(route.cost, 464);
+        assert_eq!(labels(&graph, &route), vec!["Dublin", "London"]);
+
+        let mut graph = cities();
+        graph.add_node("Rome");
+        assert_eq!(graph.shortest_path(london, 4), None);
+    }
+
+    #[test]
+    fn test_held_karp_path() {
+        let graph = cities();
+        let route = graph.held_karp(Objective::Min, Tour::Path).unwrap();
+        assert_eq!(route.cost, 300 + 100 + 141);
+        let mut ends = labels(&graph, &route);
+        if ends[0] != "London" {
+            ends.reverse();
+        }
+        assert_eq!(ends, vec!["London", "Paris", "Belfast", "Dublin"]);
+
+        let route = graph.held_karp(Objective::Max, Tour::Path).unwrap();
+        assert_eq!(route.cost, 500 + 464 + 518);
+    }
+
+    #[test]
+    fn test_held_karp_cycle() {
+        let graph = cities();
+        let route = graph.held_karp(Objective::Min, Tour::Cycle).unwrap();
+        assert_eq!(route.nodes[0], 0);
+        assert_eq!(route.cost, 464 + 141 + 100 + 300);
+        let route = graph.held_karp(Objective::Max, Tour::Cycle).unwrap();
+        assert_eq!(route.cost, 464 + 500 + 100 + 518);
+
+        // without an edge back to the start there is no cycle
+        let mut graph = Graph::new();
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        assert_eq!(
+            graph.held_karp(Objective::Min, Tour::Cycle),
+            Err(TourError::NoRoute)
+        );
+        assert_eq!(
+            graph.held_karp(Objective::Min, Tour::Path),
+            Ok(Route {
+                cost: 2,
+                nodes: vec![0, 1, 2]
+            })
+        );
+    }
+
+    #[test]
+    fn test_held_karp_limits() {
+        let graph: Graph<usize> = Graph::new();
+        assert_eq!(
+            graph.held_karp(Objective::Min, Tour::Path),
+            Err(TourError::NoRoute)
+        );
+
+        let mut graph = Graph::new();
+        for node in 0..=MAX_TOUR_NODES {
+            graph.add_undirected_edge(node, node + 1, 1);
+        }
+        assert_eq!(
+            graph.held_karp(Objective::Min, Tour::Path),
+            Err(TourError::TooManyNodes(MAX_TOUR_NODES + 2))
+        );
+    }
+}
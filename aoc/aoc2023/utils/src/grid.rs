@@ -0,0 +1,414 @@
+//! A rectangle of cells stored row after row, for the puzzles played on
+//! lights, screens and maps. Positions are `(x, y)`, `x` being the column and
+//! `y` the row, both starting at 0 in the top left corner.
+
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut, Range};
+
+use crate::parse::{ParseError, Result};
+
+pub type Pos = (usize, usize);
+
+/// Which cells are the neighbors of a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Adjacency {
+    /// Up, left, right and down.
+    Four,
+    /// Also the diagonals.
+    Eight,
+}
+
+/// What lies past the edges of a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edges {
+    /// Nothing: cells on the edges have fewer neighbors.
+    Clamp,
+    /// The other side of the grid.
+    Wrap,
+}
+
+const FOUR: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const EIGHT: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// `None` if the rows differ in length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses one row per non-blank line, with `cell` mapping characters to
+    /// cells. Lines are trimmed, so examples may be indented.
+    pub fn parse_with<F>(input: &str, cell: F) -> Result<Self>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        let mut rows = Vec::new();
+        for (idx, line) in input.lines().enumerate() {
+            let text = line.trim();
+            if text.is_empty() {
+                continue;
+            }
+            let column = line.len() - line.trim_start().len() + 1;
+            let row = text
+                .chars()
+                .enumerate()
+                .map(|(offset, c)| {
+                    cell(c).ok_or_else(|| {
+                        ParseError::new(idx + 1, column + offset, format!("invalid cell {:?}", c))
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            if rows
+                .first()
+                .is_some_and(|first: &Vec<T>| first.len() != row.len())
+            {
+                let message = format!("expected {} cells, got {}", rows[0].len(), row.len());
+                return Err(ParseError::new(idx + 1, column, message));
+            }
+            rows.push(row);
+        }
+        Ok(Self::from_rows(rows).expect("rows have the same length"))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.1 * self.width + pos.0])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        match self.contains(pos) {
+            true => Some(&mut self.cells[pos.1 * self.width + pos.0]),
+            false => None,
+        }
+    }
+
+    /// The cells row after row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// The cells with their positions, row after row.
+    pub fn enumerate(&self) -> impl Iterator<Item = (Pos, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, cell)| ((idx % width, idx / width), cell))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on 0, and a grid without columns has no cells
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn count(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        self.cells.iter().filter(|cell| predicate(cell)).count()
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The neighbors of `pos`. With [`Edges::Wrap`], a grid narrower or lower
+    /// than 3 cells gives some neighbors more than once, an empty one none.
+    pub fn neighbors(
+        &self,
+        (x, y): Pos,
+        adjacency: Adjacency,
+        edges: Edges,
+    ) -> impl Iterator<Item = Pos> {
+        let offsets: &[(isize, isize)] = match adjacency {
+            Adjacency::Four => &FOUR,
+            Adjacency::Eight => &EIGHT,
+        };
+        let (width, height) = (self.width as isize, self.height as isize);
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let (nx, ny) = (x as isize + dx, y as isize + dy);
+            match edges {
+                Edges::Wrap => nx
+                    .checked_rem_euclid(width)
+                    .zip(ny.checked_rem_euclid(height)),
+                Edges::Clamp => {
+                    ((0..width).contains(&nx) && (0..height).contains(&ny)).then_some((nx, ny))
+                }
+            }
+            .map(|(nx, ny)| (nx as usize, ny as usize))
+        })
+    }
+
+    /// Shifts row `y` right by `by` cells, the last ones coming back on the left.
+    pub fn rotate_row(&mut self, y: usize, by: usize) {
+        assert!(y < self.height, "row {} out of the grid", y);
+        let row = &mut self.cells[y * self.width..(y + 1) * self.width];
+        row.rotate_right(by.checked_rem(self.width).unwrap_or(0));
+    }
+
+    /// Shifts column `x` down by `by` cells, the last ones coming back on top.
+    pub fn rotate_column(&mut self, x: usize, by: usize) {
+        assert!(x < self.width, "column {} out of the grid", x);
+        // rotating is reversing the whole column, then both of its parts
+        let by = by.checked_rem(self.height).unwrap_or(0);
+        self.reverse_column(x, 0..self.height);
+        self.reverse_column(x, 0..by);
+        self.reverse_column(x, by..self.height);
+    }
+
+    fn reverse_column(&mut self, x: usize, ys: Range<usize>) {
+        let (mut top, mut bottom) = (ys.start, ys.end);
+        while top + 1 < bottom {
+            bottom -= 1;
+            self.cells
+                .swap(top * self.width + x, bottom * self.width + x);
+            top += 1;
+        }
+    }
+
+    /// Applies `f` to every cell of the columns `xs` of the rows `ys`.
+    pub fn update_rect(&mut self, xs: Range<usize>, ys: Range<usize>, mut f: impl FnMut(&mut T)) {
+        assert!(
+            xs.end <= self.width && ys.end <= self.height,
+            "rectangle {:?}x{:?} out of the grid",
+            xs,
+            ys
+        );
+        for y in ys {
+            for cell in &mut self.cells[y * self.width + xs.start..y * self.width + xs.end] {
+                f(cell);
+            }
+        }
+    }
+
+    pub fn fill_rect(&mut self, xs: Range<usize>, ys: Range<usize>, value: T)
+    where
+        T: Clone,
+    {
+        self.update_rect(xs, ys, |cell| *cell = value.clone());
+    }
+
+    /// A copy of the cells of the columns `xs` of the rows `ys`.
+    pub fn sub_grid(&self, xs: Range<usize>, ys: Range<usize>) -> Self
+    where
+        T: Clone,
+    {
+        assert!(
+            xs.end <= self.width && ys.end <= self.height,
+            "rectangle {:?}x{:?} out of the grid",
+            xs,
+            ys
+        );
+        let width = xs.len();
+        let height = ys.len();
+        let cells = ys
+            .flat_map(|y| self.cells[y * self.width + xs.start..y * self.width + xs.end].to_vec())
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl Grid<bool> {
+    /// Parses lights drawn with `#` for on and `.` for off.
+    pub fn parse(input: &str) -> Result<Self> {
+        Self::parse_with(input, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} out of the {}x{} grid", pos, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} out of the {}x{} grid", pos, width, height),
+        }
+    }
+}
+
+/// Draws lights the way [`Grid::parse`] reads them.
+impl Display for Grid<bool> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            let line: String = row.iter().map(|&on| if on { '#' } else { '.' }).collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u32> {
+        Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let lights = Grid::parse(
+            ".#.
+             ##.
+            ",
+        )
+        .unwrap();
+        assert_eq!((lights.width(), lights.height()), (3, 2));
+        assert!(lights[(1, 0)] && lights[(0, 1)] && !lights[(2, 1)]);
+        assert_eq!(lights.count(|&on| on), 3);
+        assert_eq!(lights.to_string(), ".#.\n##.\n");
+
+        let err = Grid::parse("..\n.x").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        let err = Grid::parse("..\n...").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn test_access() {
+        let mut grid = grid();
+        assert_eq!(grid[(2, 0)], 3);
+        assert_eq!(grid.get((3, 0)), None);
+        grid[(0, 2)] = 0;
+        assert_eq!(grid.rows().nth(2), Some(&[0, 8, 9][..]));
+        assert_eq!(grid.enumerate().nth(5), Some(((2, 1), &6)));
+        assert!(Grid::from_rows(vec![vec![1], vec![2, 3]]).is_none());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = grid();
+        let values = |adjacency, edges| -> Vec<u32> {
+            let mut values: Vec<_> = grid
+                .neighbors((0, 0), adjacency, edges)
+                .map(|pos| grid[pos])
+                .collect();
+            values.sort();
+            values
+        };
+        assert_eq!(values(Adjacency::Four, Edges::Clamp), vec![2, 4]);
+        assert_eq!(values(Adjacency::Eight, Edges::Clamp), vec![2, 4, 5]);
+        assert_eq!(values(Adjacency::Four, Edges::Wrap), vec![2, 3, 4, 7]);
+        assert_eq!(
+            values(Adjacency::Eight, Edges::Wrap),
+            vec![2, 3, 4, 5, 6, 7, 8, 9]
+        );
+        assert_eq!(
+            grid.neighbors((1, 1), Adjacency::Eight, Edges::Clamp)
+                .count(),
+            8
+        );
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut grid = grid();
+        grid.rotate_row(0, 4);
+        assert_eq!(grid.rows().next(), Some(&[3, 1, 2][..]));
+        grid.rotate_column(1, 1);
+        let column: Vec<_> = (0..3).map(|y| grid[(1, y)]).collect();
+        assert_eq!(column, vec![8, 1, 5]);
+
+        // empty grids have nothing to rotate
+        let mut narrow = Grid::new(0, 2, 0);
+        narrow.rotate_row(1, 3);
+        let mut low = Grid::new(2, 0, 0);
+        low.rotate_column(1, 3);
+    }
+
+    #[test]
+    fn test_empty_neighbors() {
+        let empty: Grid<u32> = Grid::new(0, 0, 0);
+        assert_eq!(
+            empty
+                .neighbors((0, 0), Adjacency::Four, Edges::Wrap)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_rect() {
+        let mut grid = grid();
+        grid.update_rect(1..3, 0..2, |cell| *cell *= 10);
+        assert_eq!(
+            grid.iter().sum::<u32>(),
+            1 + 20 + 30 + 4 + 50 + 60 + 7 + 8 + 9
+        );
+        grid.fill_rect(0..1, 0..3, 0);
+        assert_eq!(grid.count(|&cell| cell == 0), 3);
+
+        let sub = grid.sub_grid(1..3, 1..3);
+        assert_eq!(
+            sub,
+            Grid::from_rows(vec![vec![50, 60], vec![8, 9]]).unwrap()
+        );
+        assert_eq!(grid.map(|&cell| cell > 10).count(|&big| big), 4);
+    }
+}
@@ -0,0 +1,208 @@
+//! Where puzzle inputs come from. Inputs are never downloaded: they are kept
+//! in a cache directory with one `aoc<year>/day<NN>/input.txt` file per day,
+//! which by default is the directory holding the solutions. Examples from the
+//! puzzle texts are written inline in the tests instead.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::solution::Solution;
+
+/// Environment variable pointing the cache at another directory.
+pub const CACHE_VAR: &str = "AOC_INPUTS";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cache named by [`CACHE_VAR`], or `default` if it is not set.
+    pub fn from_env(default: impl Into<PathBuf>) -> Self {
+        match std::env::var_os(CACHE_VAR) {
+            Some(dir) => Self::new(dir),
+            None => Self::new(default),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(format!("aoc{}", year))
+            .join(format!("day{:02}", day))
+            .join("input.txt")
+    }
+
+    pub fn read(&self, year: u16, day: u8) -> io::Result<String> {
+        fs::read_to_string(self.path(year, day))
+    }
+
+    /// Stores the input of a day, replacing the cached one, and returns where.
+    pub fn store(&self, year: u16, day: u8, input: &str) -> io::Result<PathBuf> {
+        let path = self.path(year, day);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, input)?;
+        Ok(path)
+    }
+}
+
+/// Reads the input of a day from `file` if given, from the cache otherwise.
+/// Errors name the file that could not be read.
+pub fn resolve(cache: &Cache, file: Option<&Path>, year: u16, day: u8) -> Result<String, String> {
+    let path = match file {
+        Some(file) => file.to_path_buf(),
+        None => cache.path(year, day),
+    };
+    fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+/// Strips the indentation an example gets when written as a multiline string
+/// literal in a test. The first line starts right after the quote, so only
+/// the common indentation of the other lines is removed.
+pub fn example(text: &str) -> String {
+    let mut lines = text.trim_end().lines();
+    let first = lines.next().unwrap_or_default();
+    let rest: Vec<_> = lines.collect();
+    let indent = rest
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    let mut result = String::from(first);
+    for line in rest {
+        result.push('\n');
+        result += line.get(indent..).unwrap_or_default();
+    }
+    result.push('\n');
+    result
+}
+
+/// An example from a puzzle text with the answers it is given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Example {
+    /// An example without answers, see [`example`] for its indentation.
+    pub fn new(input: &str) -> Self {
+        Self {
+            input: example(input),
+            part1: None,
+            part2: None,
+        }
+    }
+
+    pub fn part1(mut self, answer: impl ToString) -> Self {
+        self.part1 = Some(answer.to_string());
+        self
+    }
+
+    pub fn part2(mut self, answer: impl ToString) -> Self {
+        self.part2 = Some(answer.to_string());
+        self
+    }
+
+    /// Solves the example with `S` and panics unless it gives the answers.
+    pub fn check<S: Solution>(&self) {
+        let input = match S::parse(&self.input) {
+            Ok(input) => input,
+            Err(e) => panic!("example does not parse: {}", e),
+        };
+        if let Some(answer) = &self.part1 {
+            assert_eq!(&S::part1(&input).to_string(), answer, "part 1");
+        }
+        if let Some(answer) = &self.part2 {
+            assert_eq!(&S::part2(&input).to_string(), answer, "part 2");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Display;
+
+    use super::*;
+    use crate::solution::Result;
+
+    #[test]
+    fn test_example() {
+        let text = "#.#
+            .#.
+
+              ##
+        ";
+        assert_eq!(example(text), "#.#\n.#.\n\n  ##\n");
+        assert_eq!(example("abc"), "abc\n");
+    }
+
+    struct Lengths;
+
+    impl Solution for Lengths {
+        type Input = Vec<String>;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            Ok(input.lines().map(str::to_owned).collect())
+        }
+
+        fn part1(input: &Self::Input) -> impl Display {
+            input.len()
+        }
+
+        fn part2(input: &Self::Input) -> impl Display {
+            input.iter().map(String::len).max().unwrap_or(0)
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        Example::new(
+            "a
+             bcd",
+        )
+        .part1(2)
+        .part2(3)
+        .check::<Lengths>();
+    }
+
+    #[test]
+    #[should_panic(expected = "part 2")]
+    fn test_check_fails() {
+        Example::new("ab").part2(1).check::<Lengths>();
+    }
+
+    #[test]
+    fn test_cache() {
+        let dir = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+        let cache = Cache::new(&dir);
+        assert_eq!(
+            cache.path(2015, 7),
+            dir.join("aoc2015").join("day07").join("input.txt")
+        );
+        assert!(cache.read(2015, 7).is_err());
+
+        let path = cache.store(2015, 7, "123 -> a\n").unwrap();
+        assert_eq!(cache.read(2015, 7).unwrap(), "123 -> a\n");
+        assert_eq!(resolve(&cache, None, 2015, 7).unwrap(), "123 -> a\n");
+        assert_eq!(resolve(&cache, Some(&path), 2016, 1).unwrap(), "123 -> a\n");
+        assert!(resolve(&cache, None, 2016, 1)
+            .unwrap_err()
+            .contains("day01"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
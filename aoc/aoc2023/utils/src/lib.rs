@@ -1 +1,8 @@
+pub mod bench;
+pub mod expected;
 pub mod files;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod solution;
@@ -0,0 +1,254 @@
+//! Parsers for the shapes puzzle inputs usually come in. Blank lines are
+//! skipped, and errors point at the line and column of the offending text.
+
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Why an input could not be parsed, and where. Lines and columns start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+/// Parses `text`, found at `line` and byte `offset` of `source`.
+fn value<T>(text: &str, source: &str, line: usize, offset: usize) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text.parse().map_err(|e| {
+        let column = source[..offset].chars().count() + 1;
+        ParseError::new(line, column, format!("invalid value {:?}: {}", text, e))
+    })
+}
+
+/// The non-blank lines of `input` with their line numbers.
+fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty())
+}
+
+/// The items of `line` separated by commas and/or whitespace, with their
+/// byte offsets.
+fn items(line: &str) -> Vec<(usize, &str)> {
+    let mut result = Vec::new();
+    let mut start = None;
+    for (idx, c) in line.char_indices() {
+        let separator = c == ',' || c.is_whitespace();
+        match (start, separator) {
+            (None, false) => start = Some(idx),
+            (Some(s), true) => {
+                result.push((s, &line[s..idx]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        result.push((s, &line[s..]));
+    }
+    result
+}
+
+/// Parses every line of `input` as a whole, e.g. into a type with its own
+/// `FromStr`.
+pub fn lines<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    numbered_lines(input)
+        .map(|(line, text)| {
+            let trimmed = text.trim();
+            let offset = text.len() - text.trim_start().len();
+            value(trimmed, text, line, offset)
+        })
+        .collect()
+}
+
+/// Parses a list of numbers separated by commas, whitespace or newlines.
+pub fn numbers<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    Ok(grid(input)?.into_iter().flatten().collect())
+}
+
+/// Parses one row per line, with the cells of a row separated by commas
+/// and/or whitespace. Rows may differ in length.
+pub fn grid<T>(input: &str) -> Result<Vec<Vec<T>>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    numbered_lines(input)
+        .map(|(line, text)| {
+            items(text)
+                .into_iter()
+                .map(|(offset, item)| value(item, text, line, offset))
+                .collect()
+        })
+        .collect()
+}
+
+/// Parses `key: value` lines, keeping their order.
+pub fn key_values<T>(input: &str) -> Result<Vec<(&str, T)>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    numbered_lines(input)
+        .map(|(line, text)| {
+            let (key, rest) = text.split_once(':').ok_or_else(|| {
+                ParseError::new(line, 1, format!("expected `key: value`, got {:?}", text))
+            })?;
+            let offset = key.len() + 1 + (rest.len() - rest.trim_start().len());
+            let parsed = value(rest.trim(), text, line, offset)?;
+            Ok((key.trim(), parsed))
+        })
+        .collect()
+}
+
+/// A part of an input separated from the others by blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block<'a> {
+    /// Line of the input the block starts at.
+    pub line: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Block<'a> {
+    /// Parses the block with `parser`, moving its errors to their line in the
+    /// whole input.
+    pub fn parse<T, F>(&self, parser: F) -> Result<T>
+    where
+        F: FnOnce(&'a str) -> Result<T>,
+    {
+        parser(self.text).map_err(|e| ParseError {
+            line: e.line + self.line - 1,
+            ..e
+        })
+    }
+}
+
+/// Splits `input` at blank lines.
+pub fn blocks(input: &str) -> Vec<Block<'_>> {
+    let mut result = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for (idx, line) in input.split_inclusive('\n').enumerate() {
+        let blank = line.trim().is_empty();
+        match (start, blank) {
+            (None, false) => start = Some((idx + 1, offset)),
+            (Some((line, begin)), true) => {
+                let text = input[begin..offset].trim_end_matches(['\n', '\r']);
+                result.push(Block { line, text });
+                start = None;
+            }
+            _ => {}
+        }
+        offset += line.len();
+    }
+    if let Some((line, begin)) = start {
+        let text = input[begin..].trim_end_matches(['\n', '\r']);
+        result.push(Block { line, text });
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(numbers::<u32>("1\n2\n\n3\n"), Ok(vec![1, 2, 3]));
+        assert_eq!(numbers::<i64>("1, -2,3  4"), Ok(vec![1, -2, 3, 4]));
+
+        let err = numbers::<u32>("1\n2 x3").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert!(err.message.contains("\"x3\""));
+    }
+
+    #[test]
+    fn test_grid() {
+        let input = "  3  4  5\n696,438,832\n";
+        assert_eq!(
+            grid::<u32>(input),
+            Ok(vec![vec![3, 4, 5], vec![696, 438, 832]])
+        );
+
+        let err = grid::<u8>("1 2\n3 300").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(lines::<char>(" a\nb\n"), Ok(vec!['a', 'b']));
+
+        let err = lines::<char>("a\n  bc").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn test_key_values() {
+        let input = "Hit Points: 109\nDamage: 8\n";
+        assert_eq!(
+            key_values::<u32>(input),
+            Ok(vec![("Hit Points", 109), ("Damage", 8)])
+        );
+
+        let err = key_values::<u32>("Damage: 8\nArmor:  two").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 9));
+        let err = key_values::<u32>("Damage 8").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "a\nb\n\n\nc\n\nd: x\n";
+        let blocks = blocks(input);
+        let texts: Vec<_> = blocks.iter().map(|b| (b.line, b.text)).collect();
+        assert_eq!(texts, vec![(1, "a\nb"), (5, "c"), (7, "d: x")]);
+
+        let err = blocks[2].parse(key_values::<u32>).unwrap_err();
+        assert_eq!((err.line, err.column), (7, 4));
+    }
+
+    #[test]
+    fn test_error_display() {
+        let err = ParseError::new(3, 7, "invalid value");
+        assert_eq!(err.to_string(), "line 3, column 7: invalid value");
+    }
+}
@@ -0,0 +1,137 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// A day's puzzle. The input is parsed once and shared by both parts.
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> impl Display;
+    fn part2(input: &Self::Input) -> impl Display;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: String,
+    pub part2: String,
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Part 1 = {}", self.part1)?;
+        write!(f, "Part 2 = {}", self.part2)
+    }
+}
+
+/// How long each step of solving a day took.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+/// Parses `input` and solves both parts of `S`.
+pub fn solve<S: Solution>(input: &str) -> Result<Answers> {
+    solve_timed::<S>(input).map(|(answers, _)| answers)
+}
+
+/// Like [`solve`], also timing the parsing and each part.
+pub fn solve_timed<S: Solution>(input: &str) -> Result<(Answers, Timings)> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let part1 = S::part1(&input).to_string();
+    let part1_time = start.elapsed();
+
+    let start = Instant::now();
+    let part2 = S::part2(&input).to_string();
+    let part2_time = start.elapsed();
+
+    let timings = Timings {
+        parse,
+        part1: part1_time,
+        part2: part2_time,
+    };
+    Ok((Answers { part1, part2 }, timings))
+}
+
+/// A solution registered under its year and day, so it can be run without
+/// naming its type.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    solve: fn(&str) -> Result<(Answers, Timings)>,
+}
+
+impl Day {
+    pub fn new<S: Solution>(year: u16, day: u8) -> Self {
+        Self {
+            year,
+            day,
+            solve: solve_timed::<S>,
+        }
+    }
+
+    pub fn solve(&self, input: &str) -> Result<Answers> {
+        self.solve_timed(input).map(|(answers, _)| answers)
+    }
+
+    pub fn solve_timed(&self, input: &str) -> Result<(Answers, Timings)> {
+        (self.solve)(input)
+    }
+}
+
+impl fmt::Debug for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day({}, {})", self.year, self.day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sums;
+
+    impl Solution for Sums {
+        type Input = Vec<i32>;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            let mut numbers = Vec::new();
+            for line in input.lines() {
+                numbers.push(line.trim().parse()?);
+            }
+            Ok(numbers)
+        }
+
+        fn part1(input: &Self::Input) -> impl Display {
+            input.iter().sum::<i32>()
+        }
+
+        fn part2(input: &Self::Input) -> impl Display {
+            input.iter().map(|n| n * n).sum::<i32>()
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let answers = solve::<Sums>("1\n2\n3").unwrap();
+        assert_eq!(answers.part1, "6");
+        assert_eq!(answers.part2, "14");
+        assert!(solve::<Sums>("1\nx").is_err());
+    }
+
+    #[test]
+    fn test_registered_day() {
+        let day = Day::new::<Sums>(2015, 1);
+        assert_eq!((day.year, day.day), (2015, 1));
+        assert_eq!(day.solve("4").unwrap().part2, "16");
+    }
+}
@@ -0,0 +1,244 @@
+mod registry;
+mod scaffold;
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use utils::bench::{self, Report};
+use utils::expected::Expected;
+use utils::input::{self, Cache};
+use utils::solution::{Answers, Day};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solves a day, or every solved day of a year, with its puzzle input
+    Run {
+        #[arg(long)]
+        year: u16,
+        #[arg(long)]
+        day: Option<u8>,
+        /// Reads the input from this file instead of the cache
+        #[arg(long, requires = "day")]
+        input: Option<PathBuf>,
+    },
+    /// Times the parsing and both parts of a day, or of every solved day of a year
+    Bench {
+        #[arg(long)]
+        year: u16,
+        #[arg(long)]
+        day: Option<u8>,
+        /// Reads the input from this file instead of the cache
+        #[arg(long, requires = "day")]
+        input: Option<PathBuf>,
+        /// How many times each day is solved
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        runs: u32,
+        /// Prints a Markdown table instead of plain text
+        #[arg(long)]
+        markdown: bool,
+    },
+    /// Checks the answers of every solved day, or of a year or day, against the recorded ones
+    Verify {
+        #[arg(long)]
+        year: Option<u16>,
+        #[arg(long, requires = "year")]
+        day: Option<u8>,
+        /// Records the answers instead of failing when they differ
+        #[arg(long)]
+        update: bool,
+    },
+    /// Creates the crate of a new day and registers it
+    New {
+        #[arg(long)]
+        year: u16,
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Copies a downloaded puzzle input into the cache
+    Import {
+        #[arg(long)]
+        year: u16,
+        #[arg(long)]
+        day: u8,
+        file: PathBuf,
+    },
+}
+
+/// The directory holding every year.
+fn aoc_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+/// The puzzle inputs, kept next to the sources of each day unless
+/// `AOC_INPUTS` points elsewhere.
+fn cache() -> Cache {
+    Cache::from_env(aoc_dir())
+}
+
+fn read_input(day: &Day, file: Option<&Path>) -> Result<String, String> {
+    input::resolve(&cache(), file, day.year, day.day)
+}
+
+fn solve(day: &Day, file: Option<&Path>) -> Result<Answers, String> {
+    let input = read_input(day, file)?;
+    day.solve(&input).map_err(|e| e.to_string())
+}
+
+fn run(day: &Day, file: Option<&Path>) -> Result<(), String> {
+    let answers = solve(day, file)?;
+    println!("{} day {:02}", day.year, day.day);
+    println!("{}", answers);
+    Ok(())
+}
+
+fn bench(day: &Day, file: Option<&Path>, runs: u32) -> Result<Report, String> {
+    let input = read_input(day, file)?;
+    bench::bench(day, &input, runs as usize).map_err(|e| e.to_string())
+}
+
+fn read_expected(path: &Path) -> Result<Expected, String> {
+    match fs::read_to_string(path) {
+        Ok(text) => Expected::parse(&text).map_err(|e| format!("{}: {}", path.display(), e)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Expected::default()),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+/// Compares the answers of `days` with the recorded ones, or records them if
+/// `update` is set. Returns whether every answer matched.
+fn verify(days: &[Day], update: bool) -> Result<bool, String> {
+    let path = aoc_dir().join("answers.txt");
+    let mut expected = read_expected(&path)?;
+    let mut matched = true;
+
+    for day in days {
+        let fail = |e| format!("{} day {:02}: {}", day.year, day.day, e);
+        let input = read_input(day, None).map_err(fail)?;
+        if input.trim().is_empty() {
+            println!("{} day {:02}: skipped, no input", day.year, day.day);
+            continue;
+        }
+        let answers = day.solve(&input).map_err(|e| fail(e.to_string()))?;
+        let differences = expected.verify(day.year, day.day, &answers);
+        if differences.is_empty() {
+            println!("{} day {:02}: ok", day.year, day.day);
+            continue;
+        }
+        for difference in &differences {
+            println!("{} day {:02}: {}", day.year, day.day, difference);
+        }
+        if update {
+            expected.record(day.year, day.day, &answers);
+        } else {
+            matched = false;
+        }
+    }
+
+    if update {
+        fs::write(&path, expected.to_string())
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+    Ok(matched)
+}
+
+fn new_day(year: u16, day: u8) -> ExitCode {
+    match scaffold::generate(aoc_dir(), year, day) {
+        Ok(files) => {
+            for file in files {
+                println!("Wrote {}", file.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn fail(day: &Day, e: String) -> ExitCode {
+    eprintln!("{} day {:02}: {}", day.year, day.day, e);
+    ExitCode::FAILURE
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let days = match cli.command {
+        Command::Run { year, day, .. } | Command::Bench { year, day, .. } => {
+            registry::select(Some(year), day)
+        }
+        Command::Import { year, day, .. } => registry::select(Some(year), Some(day)),
+        Command::Verify { year, day, .. } => registry::select(year, day),
+        Command::New { year, day } => return new_day(year, day),
+    };
+    if days.is_empty() {
+        eprintln!("No solution found");
+        return ExitCode::FAILURE;
+    }
+
+    match cli.command {
+        Command::Run { input, .. } => {
+            for day in &days {
+                if let Err(e) = run(day, input.as_deref()) {
+                    return fail(day, e);
+                }
+            }
+        }
+        Command::Bench {
+            input,
+            runs,
+            markdown,
+            ..
+        } => {
+            let mut reports = Vec::new();
+            for day in &days {
+                match bench(day, input.as_deref(), runs) {
+                    Ok(report) if markdown => reports.push(report),
+                    Ok(report) => print!("{}", bench::text(&[report])),
+                    Err(e) => return fail(day, e),
+                }
+            }
+            if markdown {
+                print!("{}", bench::markdown(&reports));
+            }
+        }
+        Command::Verify { update, .. } => match verify(&days, update) {
+            Ok(true) => {}
+            Ok(false) => {
+                eprintln!("Answers changed, run with --update if that is expected");
+                return ExitCode::FAILURE;
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        Command::New { .. } => unreachable!(),
+        Command::Import { file, .. } => {
+            let day = &days[0];
+            let stored = fs::read_to_string(&file)
+                .map_err(|e| format!("Failed to read {}: {}", file.display(), e))
+                .and_then(|input| {
+                    cache()
+                        .store(day.year, day.day, &input)
+                        .map_err(|e| format!("Failed to store the input: {}", e))
+                });
+            match stored {
+                Ok(path) => println!("Stored {}", path.display()),
+                Err(e) => return fail(day, e),
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,81 @@
+use utils::solution::Day;
+
+macro_rules! days {
+    ($($solution:path => ($year:literal, $day:literal),)*) => {
+        vec![$(Day::new::<$solution>($year, $day),)*]
+    };
+}
+
+/// Every solved day, ordered by year and day.
+pub fn days() -> Vec<Day> {
+    days![
+        aoc2015_day01::Day01 => (2015, 1),
+        aoc2015_day02::Day02 => (2015, 2),
+        aoc2015_day03::Day03 => (2015, 3),
+        aoc2015_day04::Day04 => (2015, 4),
+        aoc2015_day05::Day05 => (2015, 5),
+        aoc2015_day06::Day06 => (2015, 6),
+        aoc2015_day07::Day07 => (2015, 7),
+        aoc2015_day08::Day08 => (2015, 8),
+        aoc2015_day09::Day09 => (2015, 9),
+        aoc2015_day10::Day10 => (2015, 10),
+        aoc2015_day11::Day11 => (2015, 11),
+        aoc2015_day12::Day12 => (2015, 12),
+        aoc2015_day13::Day13 => (2015, 13),
+        aoc2015_day14::Day14 => (2015, 14),
+        aoc2015_day15::Day15 => (2015, 15),
+        aoc2015_day16::Day16 => (2015, 16),
+        aoc2015_day17::Day17 => (2015, 17),
+        aoc2015_day18::Day18 => (2015, 18),
+        aoc2015_day19::Day19 => (2015, 19),
+        aoc2015_day20::Day20 => (2015, 20),
+        aoc2015_day21::Day21 => (2015, 21),
+        aoc2015_day22::Day22 => (2015, 22),
+        aoc2015_day23::Day23 => (2015, 23),
+        aoc2015_day24::Day24 => (2015, 24),
+        aoc2015_day25::Day25 => (2015, 25),
+        aoc2016_day01::Day01 => (2016, 1),
+        aoc2016_day02::Day02 => (2016, 2),
+        aoc2016_day03::Day03 => (2016, 3),
+        aoc2016_day04::Day04 => (2016, 4),
+        aoc2016_day05::Day05 => (2016, 5),
+        aoc2016_day06::Day06 => (2016, 6),
+        aoc2016_day07::Day07 => (2016, 7),
+        aoc2016_day08::Day08 => (2016, 8),
+        aoc2016_day09::Day09 => (2016, 9),
+        aoc2016_day10::Day10 => (2016, 10),
+        aoc2023_day01::Day01 => (2023, 1),
+        aoc2023_day02::Day02 => (2023, 2),
+    ]
+}
+
+/// The solved days of `year`, or only `day` of it when given. Every solved
+/// day without a year.
+pub fn select(year: Option<u16>, day: Option<u8>) -> Vec<Day> {
+    days()
+        .into_iter()
+        .filter(|d| year.is_none_or(|year| d.year == year))
+        .filter(|d| day.is_none_or(|day| d.day == day))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_ordered_and_unique() {
+        let keys: Vec<_> = days().iter().map(|d| (d.year, d.day)).collect();
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_select() {
+        let selected = select(Some(2015), Some(7));
+        assert_eq!(selected.len(), 1);
+        assert_eq!((selected[0].year, selected[0].day), (2015, 7));
+        assert_eq!(select(Some(2015), None).len(), 25);
+        assert_eq!(select(None, None).len(), days().len());
+        assert!(select(Some(2015), Some(26)).is_empty());
+    }
+}
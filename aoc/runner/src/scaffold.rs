@@ -0,0 +1,280 @@
+//! Generating the crate of a new day and registering it with its year's
+//! workspace and with the runner.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the crate of a day, as a package and as a path.
+fn crate_name(year: u16, day: u8, separator: char) -> String {
+    format!("aoc{}{}day{:02}", year, separator, day)
+}
+
+/// The manifest of a year that has no day yet.
+pub fn workspace_manifest() -> String {
+    "[workspace]
+resolver = \"2\"
+members = [
+]
+
+[workspace.dependencies]
+utils = { path = \"../aoc2023/utils\" }
+"
+    .to_owned()
+}
+
+/// The manifest of a day. `utils` comes from the workspace when it declares
+/// it, like in every year but 2023 which holds `utils` itself.
+pub fn package_manifest(year: u16, day: u8, workspace: &str) -> String {
+    let utils = match workspace.contains("[workspace.dependencies]") {
+        true => "{ workspace = true }",
+        false => "{ path = \"../utils\" }",
+    };
+    format!(
+        "[package]
+name = \"{}\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+utils = {}
+",
+        crate_name(year, day, '-'),
+        utils
+    )
+}
+
+/// A solution answering 0 to both parts, with a test for the example of the
+/// puzzle text that is ignored until it is filled in.
+pub fn library(day: u8) -> String {
+    format!(
+        "use std::fmt::Display;
+
+use utils::parse;
+use utils::solution::{{Result, Solution}};
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input> {{
+        Ok(parse::lines(input)?)
+    }}
+
+    fn part1(_input: &Self::Input) -> impl Display {{
+        0
+    }}
+
+    fn part2(_input: &Self::Input) -> impl Display {{
+        0
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use utils::input::Example;
+
+    #[test]
+    #[ignore = \"the example is not filled in yet\"]
+    fn test_example() {{
+        // TODO: the example of the puzzle text and the answers it is given,
+        // then drop the `#[ignore]`.
+        Example::new(\"\").part1(0).part2(0).check::<Day{day:02}>();
+    }}
+}}
+"
+    )
+}
+
+/// Inserts `line` among the lines of `text` for which `is_entry` holds,
+/// keeping them sorted. `after` is the line the entries follow when there
+/// are none yet. Nothing changes if the line is already there.
+fn insert_sorted(
+    text: &str,
+    line: &str,
+    is_entry: impl Fn(&str) -> bool,
+    after: &str,
+) -> Result<String, String> {
+    let lines: Vec<_> = text.lines().collect();
+    if lines.contains(&line) {
+        return Ok(text.to_owned());
+    }
+    let position = match lines.iter().position(|l| is_entry(l) && *l > line) {
+        Some(position) => position,
+        None => match lines.iter().rposition(|l| is_entry(l)) {
+            Some(last) => last + 1,
+            None => {
+                let idx = lines
+                    .iter()
+                    .position(|l| l.trim_end() == after)
+                    .ok_or_else(|| format!("expected a `{}` line", after))?;
+                idx + 1
+            }
+        },
+    };
+
+    let mut result = String::new();
+    for (idx, l) in lines.iter().enumerate() {
+        if idx == position {
+            result += line;
+            result.push('\n');
+        }
+        result += l;
+        result.push('\n');
+    }
+    if position == lines.len() {
+        result += line;
+        result.push('\n');
+    }
+    if !text.ends_with('\n') {
+        result.pop();
+    }
+    Ok(result)
+}
+
+/// Adds `day` to the members of a year's workspace.
+pub fn add_member(workspace: &str, day: u8) -> Result<String, String> {
+    let line = format!("        \"day{:02}\",", day);
+    let is_member = |l: &str| l.starts_with("        \"");
+    insert_sorted(workspace, &line, is_member, "members = [")
+}
+
+/// Adds a day to the dependencies of the runner.
+pub fn add_dependency(runner: &str, year: u16, day: u8) -> Result<String, String> {
+    let line = format!(
+        "{} = {{ path = \"../aoc{}/day{:02}\" }}",
+        crate_name(year, day, '-'),
+        year,
+        day
+    );
+    let is_day = |l: &str| l.starts_with("aoc");
+    insert_sorted(runner, &line, is_day, "[dependencies]")
+}
+
+/// Adds a day to the list in `registry.rs`.
+pub fn register(registry: &str, year: u16, day: u8) -> Result<String, String> {
+    let line = format!(
+        "        {}::Day{:02} => ({}, {}),",
+        crate_name(year, day, '_'),
+        day,
+        year,
+        day
+    );
+    let is_day = |l: &str| l.starts_with("        aoc");
+    insert_sorted(registry, &line, is_day, "    days![")
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+fn write(path: &Path, text: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, text).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Creates the crate of a day under `aoc_dir`, with an empty input, and
+/// registers it. Returns the files written.
+pub fn generate(aoc_dir: &Path, year: u16, day: u8) -> Result<Vec<PathBuf>, String> {
+    let year_dir = aoc_dir.join(format!("aoc{}", year));
+    let day_dir = year_dir.join(format!("day{:02}", day));
+    if day_dir.exists() {
+        return Err(format!("{} already exists", day_dir.display()));
+    }
+
+    let workspace_path = year_dir.join("Cargo.toml");
+    let workspace = match workspace_path.exists() {
+        true => read(&workspace_path)?,
+        false => workspace_manifest(),
+    };
+    let runner_path = aoc_dir.join("runner").join("Cargo.toml");
+    let registry_path = aoc_dir.join("runner").join("src").join("registry.rs");
+
+    let files = [
+        (
+            day_dir.join("Cargo.toml"),
+            package_manifest(year, day, &workspace),
+        ),
+        (day_dir.join("src").join("lib.rs"), library(day)),
+        (day_dir.join("input.txt"), String::new()),
+        (workspace_path, add_member(&workspace, day)?),
+        (
+            runner_path.clone(),
+            add_dependency(&read(&runner_path)?, year, day)?,
+        ),
+        (
+            registry_path.clone(),
+            register(&read(&registry_path)?, year, day)?,
+        ),
+    ];
+    for (path, text) in &files {
+        write(path, text)?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_member() {
+        let workspace = "[workspace]\nmembers = [\n        \"day01\",\n        \"day03\",\n        \"utils\",\n]\n";
+        let added = add_member(workspace, 2).unwrap();
+        assert!(added.contains("\"day01\",\n        \"day02\",\n        \"day03\","));
+        assert_eq!(add_member(&added, 2).unwrap(), added);
+
+        let added = add_member(&workspace_manifest(), 1).unwrap();
+        assert!(added.contains("members = [\n        \"day01\",\n]"));
+    }
+
+    #[test]
+    fn test_register() {
+        let registry = "    days![\n        aoc2015_day01::Day01 => (2015, 1),\n        aoc2023_day01::Day01 => (2023, 1),\n    ]\n";
+        let added = register(registry, 2016, 11).unwrap();
+        let lines: Vec<_> = added.lines().collect();
+        assert_eq!(lines[2], "        aoc2016_day11::Day11 => (2016, 11),");
+
+        let added = register(&added, 2023, 2).unwrap();
+        assert_eq!(
+            added.lines().nth(4),
+            Some("        aoc2023_day02::Day02 => (2023, 2),")
+        );
+        assert!(register("", 2023, 2).is_err());
+    }
+
+    #[test]
+    fn test_add_dependency() {
+        let runner = "[dependencies]\nclap = \"4.5\"\n";
+        let added = add_dependency(runner, 2015, 2).unwrap();
+        assert_eq!(
+            added,
+            "[dependencies]\naoc2015-day02 = { path = \"../aoc2015/day02\" }\nclap = \"4.5\"\n"
+        );
+        let added = add_dependency(&added, 2015, 1).unwrap();
+        assert!(added.contains("day01\" }\naoc2015-day02"));
+    }
+
+    #[test]
+    fn test_package_manifest() {
+        let manifest = package_manifest(2015, 26, &workspace_manifest());
+        assert!(manifest.contains("name = \"aoc2015-day26\""));
+        assert!(manifest.contains("utils = { workspace = true }"));
+        let manifest = package_manifest(2023, 2, "[workspace]\n");
+        assert!(manifest.contains("utils = { path = \"../utils\" }"));
+    }
+
+    #[test]
+    fn test_library() {
+        let library = library(2);
+        assert!(library.contains("pub struct Day02;"));
+        // the stub must not pass its example test
+        assert!(library.contains("#[ignore = "));
+    }
+}
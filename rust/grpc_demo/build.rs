@@ -1,11 +1,27 @@
 use std::{env, path::PathBuf};
 
+/// `google.protobuf.Timestamp` fields, which need a serde helper for JSON.
+const TIMESTAMP_FIELDS: &[&str] = &[
+    "bookstore.Book.created_at",
+    "bookstore.Book.updated_at",
+    "bookstore.AuditEvent.time",
+    "bookstore.ListAuditEventsRequest.start_time",
+    "bookstore.ListAuditEventsRequest.end_time",
+];
+
 fn main() {
     let proto_file = "./proto/bookstore.proto";
     let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
 
-    tonic_build::configure()
+    let mut builder = tonic_build::configure()
         .build_server(true)
+        .type_attribute(".", "#[derive(serde::Serialize, serde::Deserialize)]")
+        .type_attribute(".", "#[serde(default, rename_all = \"camelCase\")]");
+    for field in TIMESTAMP_FIELDS {
+        builder = builder.field_attribute(field, "#[serde(with = \"crate::json::timestamp\")]");
+    }
+
+    builder
         .file_descriptor_set_path(out_dir.join("greeter_descriptor.bin"))
         .out_dir("./src")
         .compile(&[proto_file], &["."])
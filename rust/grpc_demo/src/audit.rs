@@ -0,0 +1,275 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::net::SocketAddr;
+use std::path::Path;
+
+use prost_types::Timestamp;
+use tonic::Request;
+
+use crate::bookstore::{AuditEvent, ListAuditEventsRequest};
+use crate::tenant::DEFAULT_TENANT;
+
+/// Request metadata naming the user behind a write, as claimed by the client.
+pub const USER_METADATA: &str = "x-user";
+
+/// Request extension with the address of a peer the server did not get the
+/// request from directly, set by the REST gateway. Clients cannot send it.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Peer(pub SocketAddr);
+
+/// Who a write came from. The actor is the peer address, which unlike the
+/// claimed `x-user` the client cannot choose.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Origin {
+    pub actor: String,
+    pub claimed_user: String,
+}
+
+impl Origin {
+    pub fn of<T>(request: &Request<T>) -> Self {
+        let peer = request
+            .remote_addr()
+            .or_else(|| request.extensions().get::<Peer>().map(|peer| peer.0));
+        let claimed_user = request
+            .metadata()
+            .get(USER_METADATA)
+            .and_then(|user| user.to_str().ok());
+        Origin {
+            actor: peer.map_or_else(|| "unknown".to_owned(), |addr| addr.to_string()),
+            claimed_user: claimed_user.unwrap_or_default().to_owned(),
+        }
+    }
+}
+
+pub const CREATE: &str = "create";
+pub const UPDATE: &str = "update";
+pub const DELETE: &str = "delete";
+pub const CREATE_TENANT: &str = "create_tenant";
+pub const DELETE_TENANT: &str = "delete_tenant";
+
+/// Append-only record of catalogue writes. Events are kept in memory for
+/// queries and, for a log opened from a file, appended to it as JSON lines.
+#[derive(Debug, Default)]
+pub struct AuditLog {
+    file: Option<File>,
+    events: Vec<AuditEvent>,
+}
+
+fn key(timestamp: &Timestamp) -> (i64, i32) {
+    (timestamp.seconds, timestamp.nanos)
+}
+
+/// Reads every event of a log file, which is the source of truth for them.
+/// A last line cut short by a failed append is removed from the file.
+fn load(file: &mut File) -> io::Result<Vec<AuditEvent>> {
+    let mut text = String::new();
+    file.seek(SeekFrom::Start(0))?;
+    file.read_to_string(&mut text)?;
+    let complete = text.rfind('\n').map_or(0, |end| end + 1);
+    if complete < text.len() {
+        file.set_len(complete as u64)?;
+        text.truncate(complete);
+    }
+
+    let mut events = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut event: AuditEvent = serde_json::from_str(line).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("audit log line {}: {}", number + 1, e),
+            )
+        })?;
+        // written before the server had tenants
+        if event.tenant.is_empty() {
+            event.tenant = DEFAULT_TENANT.to_owned();
+        }
+        events.push(event);
+    }
+    Ok(events)
+}
+
+impl AuditLog {
+    /// A log that is lost when the server stops.
+    pub fn in_memory() -> Self {
+        AuditLog::default()
+    }
+
+    /// Opens the log at `path`, creating it if needed and loading the events
+    /// already written to it.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+        let events = load(&mut file)?;
+        Ok(AuditLog {
+            file: Some(file),
+            events,
+        })
+    }
+
+    /// Records `event` under the next sequence number. When writing to the
+    /// file fails, the events are reloaded from it: the event is only kept if
+    /// it made it there.
+    pub(crate) fn append(&mut self, mut event: AuditEvent) -> io::Result<()> {
+        event.sequence = self.next_sequence();
+        if let Some(file) = &mut self.file {
+            let mut line = serde_json::to_vec(&event)?;
+            line.push(b'\n');
+            if let Err(error) = file.write_all(&line).and_then(|_| file.sync_data()) {
+                self.events = load(file)?;
+                return Err(error);
+            }
+        }
+        self.events.push(event);
+        Ok(())
+    }
+
+    /// The sequence number the next event is recorded under.
+    pub(crate) fn next_sequence(&self) -> u64 {
+        self.events.last().map_or(1, |last| last.sequence + 1)
+    }
+
+    /// Events of the tenant `tenant` with the id `tenant_id` matching
+    /// `request`, leaving out those of earlier tenants of the same name.
+    pub(crate) fn query(
+        &self,
+        tenant: &str,
+        tenant_id: u64,
+        request: &ListAuditEventsRequest,
+    ) -> Vec<AuditEvent> {
+        self.events
+            .iter()
+            .filter(|event| event.tenant == tenant && event.tenant_id == tenant_id)
+            .filter(|event| request.book_id.is_empty() || event.book_id == request.book_id)
+            .filter(|event| {
+                let time = event.time.as_ref().map(key).unwrap_or_default();
+                let after_start = request
+                    .start_time
+                    .as_ref()
+                    .is_none_or(|start| time >= key(start));
+                let before_end = request.end_time.as_ref().is_none_or(|end| time < key(end));
+                after_start && before_end
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use super::*;
+    use crate::bookstore::Book;
+
+    fn event(seconds: i64, book_id: &str, action: &str) -> AuditEvent {
+        let book = Book {
+            id: book_id.to_owned(),
+            ..Book::default()
+        };
+        AuditEvent {
+            time: Some(Timestamp { seconds, nanos: 0 }),
+            actor: "tester".to_owned(),
+            action: action.to_owned(),
+            book_id: book_id.to_owned(),
+            tenant: DEFAULT_TENANT.to_owned(),
+            before: (action != CREATE).then(|| book.clone()),
+            after: (action != DELETE).then_some(book),
+            ..AuditEvent::default()
+        }
+    }
+
+    fn sequences(events: Vec<AuditEvent>) -> Vec<u64> {
+        events.into_iter().map(|event| event.sequence).collect()
+    }
+
+    #[test]
+    fn queries_filter_by_book_and_time_range() {
+        let mut log = AuditLog::in_memory();
+        log.append(event(10, "a", CREATE)).unwrap();
+        log.append(event(20, "b", CREATE)).unwrap();
+        log.append(event(30, "a", UPDATE)).unwrap();
+        log.append(event(40, "a", DELETE)).unwrap();
+
+        let all = ListAuditEventsRequest::default();
+        assert_eq!(sequences(log.query(DEFAULT_TENANT, 0, &all)), [1, 2, 3, 4]);
+
+        let book_a = ListAuditEventsRequest {
+            book_id: "a".to_owned(),
+            ..all.clone()
+        };
+        assert_eq!(sequences(log.query(DEFAULT_TENANT, 0, &book_a)), [1, 3, 4]);
+
+        let range = ListAuditEventsRequest {
+            start_time: Some(Timestamp {
+                seconds: 20,
+                nanos: 0,
+            }),
+            end_time: Some(Timestamp {
+                seconds: 40,
+                nanos: 0,
+            }),
+            ..book_a
+        };
+        assert_eq!(sequences(log.query(DEFAULT_TENANT, 0, &range)), [3]);
+    }
+
+    #[test]
+    fn file_log_survives_reopening() {
+        let path = env::temp_dir().join(format!("grpc_demo_audit_{}.log", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut log = AuditLog::open(&path).unwrap();
+        log.append(event(10, "a", CREATE)).unwrap();
+        log.append(event(20, "a", UPDATE)).unwrap();
+        drop(log);
+
+        let mut log = AuditLog::open(&path).unwrap();
+        log.append(event(30, "a", DELETE)).unwrap();
+        let events = log.query(DEFAULT_TENANT, 0, &ListAuditEventsRequest::default());
+        assert_eq!(sequences(events.clone()), [1, 2, 3]);
+        assert_eq!(events[1], {
+            let mut expected = event(20, "a", UPDATE);
+            expected.sequence = 2;
+            expected
+        });
+
+        let mut other = event(50, "a", CREATE);
+        other.tenant = "acme".to_owned();
+        other.tenant_id = 7;
+        log.append(other).unwrap();
+        let events = log.query("acme", 7, &ListAuditEventsRequest::default());
+        assert_eq!(sequences(events), [4]);
+        assert!(log
+            .query("acme", 8, &ListAuditEventsRequest::default())
+            .is_empty());
+
+        let lines = fs::read_to_string(&path).unwrap();
+        assert_eq!(lines.lines().count(), 4);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn lines_cut_short_are_dropped() {
+        let path = env::temp_dir().join(format!("grpc_demo_torn_{}.log", std::process::id()));
+        let mut first = event(10, "a", CREATE);
+        first.sequence = 1;
+        let mut line = serde_json::to_string(&first).unwrap();
+        line.push('\n');
+        fs::write(&path, format!("{}{{\"sequence\":2,\"ti", line)).unwrap();
+
+        let mut log = AuditLog::open(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), line);
+        log.append(event(20, "a", UPDATE)).unwrap();
+        let events = log.query(DEFAULT_TENANT, 0, &ListAuditEventsRequest::default());
+        assert_eq!(sequences(events), [1, 2]);
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 2);
+        fs::remove_file(&path).unwrap();
+    }
+}
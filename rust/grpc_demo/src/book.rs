@@ -0,0 +1,104 @@
+use crate::bookstore::Book;
+use crate::isbn;
+
+/// Checks a book sent by a client and normalizes its fields: whitespace is
+/// trimmed, tags are lowercased and deduplicated and the ISBN loses its
+/// separators. Timestamps are left for the caller to set.
+pub(crate) fn validate(mut book: Book) -> Result<Book, String> {
+    book.title = book.title.trim().to_owned();
+    if book.title.is_empty() {
+        return Err("title must not be empty".to_owned());
+    }
+
+    book.authors = book
+        .authors
+        .iter()
+        .map(|author| author.trim().to_owned())
+        .collect();
+    if book.authors.is_empty() || book.authors.iter().any(String::is_empty) {
+        return Err(
+            "a book needs at least one author and author names must not be empty".to_owned(),
+        );
+    }
+
+    if !book.isbn.is_empty() {
+        book.isbn = isbn::normalize(&book.isbn)
+            .ok_or_else(|| format!("{:?} is not a valid ISBN-10 or ISBN-13", book.isbn))?;
+    }
+
+    let mut tags: Vec<String> = Vec::with_capacity(book.tags.len());
+    for tag in &book.tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() {
+            return Err("tags must not be empty".to_owned());
+        }
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    book.tags = tags;
+
+    book.publisher = book.publisher.trim().to_owned();
+
+    if book.year < 0 {
+        return Err("year must not be negative".to_owned());
+    }
+
+    Ok(book)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book() -> Book {
+        Book {
+            id: "rust-book".to_owned(),
+            title: " The Rust Programming Language ".to_owned(),
+            authors: vec!["Steve Klabnik".to_owned(), "Carol Nichols ".to_owned()],
+            isbn: "978-1-59327-828-1".to_owned(),
+            tags: vec![
+                "Rust".to_owned(),
+                "programming".to_owned(),
+                "rust".to_owned(),
+            ],
+            publisher: "No Starch Press".to_owned(),
+            year: 2018,
+            ..Book::default()
+        }
+    }
+
+    #[test]
+    fn normalizes_a_valid_book() {
+        let book = validate(book()).unwrap();
+        assert_eq!(book.title, "The Rust Programming Language");
+        assert_eq!(book.authors, ["Steve Klabnik", "Carol Nichols"]);
+        assert_eq!(book.isbn, "9781593278281");
+        assert_eq!(book.tags, ["rust", "programming"]);
+    }
+
+    #[test]
+    fn rejects_invalid_fields() {
+        let cases = [
+            Book {
+                title: "  ".to_owned(),
+                ..book()
+            },
+            Book {
+                authors: vec![],
+                ..book()
+            },
+            Book {
+                isbn: "978-1-59327-828-2".to_owned(),
+                ..book()
+            },
+            Book {
+                tags: vec!["".to_owned()],
+                ..book()
+            },
+        ];
+        for case in cases {
+            assert!(validate(case).is_err());
+        }
+    }
+}
@@ -1,18 +1,216 @@
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Book {
+    #[prost(string, tag="1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub title: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag="3")]
+    pub authors: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// ISBN-10 or ISBN-13, stored without separators
+    #[prost(string, tag="4")]
+    pub isbn: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag="5")]
+    pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, tag="6")]
+    pub publisher: ::prost::alloc::string::String,
+    #[prost(int32, tag="7")]
+    pub year: i32,
+    /// set by the server
+    #[prost(message, optional, tag="8")]
+    #[serde(with = "crate::json::timestamp")]
+    pub created_at: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag="9")]
+    #[serde(with = "crate::json::timestamp")]
+    pub updated_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// changes on every write, updates and deletes must send the current value
+    #[prost(string, tag="10")]
+    pub etag: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetBookRequest {
     #[prost(string, tag="1")]
     pub id: ::prost::alloc::string::String,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListBooksRequest {
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListBooksResponse {
+    #[prost(message, repeated, tag="1")]
+    pub books: ::prost::alloc::vec::Vec<Book>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateBookRequest {
+    /// an id is assigned by the server when left empty
+    #[prost(message, optional, tag="1")]
+    pub book: ::core::option::Option<Book>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateBookRequest {
+    /// `book.etag` must match the stored book
+    #[prost(message, optional, tag="1")]
+    pub book: ::core::option::Option<Book>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetBookResponse {
+pub struct DeleteBookRequest {
     #[prost(string, tag="1")]
     pub id: ::prost::alloc::string::String,
+    /// must match the stored book
     #[prost(string, tag="2")]
-    pub name: ::prost::alloc::string::String,
+    pub etag: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchBooksRequest {
+    /// free text matched case-insensitively against titles and authors, a word
+    /// also matches longer words it is a prefix of
+    #[prost(string, tag="1")]
+    pub query: ::prost::alloc::string::String,
+    /// maximum number of results, 0 returns every match
+    #[prost(int32, tag="2")]
+    pub limit: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchResult {
+    #[prost(message, optional, tag="1")]
+    pub book: ::core::option::Option<Book>,
+    #[prost(double, tag="2")]
+    pub score: f64,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchBooksResponse {
+    /// best matches first
+    #[prost(message, repeated, tag="1")]
+    pub results: ::prost::alloc::vec::Vec<SearchResult>,
+}
+/// A write to the catalogue.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AuditEvent {
+    /// increases by one with every event
+    #[prost(uint64, tag="1")]
+    pub sequence: u64,
+    #[prost(message, optional, tag="2")]
+    #[serde(with = "crate::json::timestamp")]
+    pub time: ::core::option::Option<::prost_types::Timestamp>,
+    /// the peer address the write came from, as seen by the server
     #[prost(string, tag="3")]
-    pub author: ::prost::alloc::string::String,
-    #[prost(int32, tag="4")]
-    pub year: i32,
+    pub actor: ::prost::alloc::string::String,
+    /// "create", "update" or "delete" for books, "create_tenant" or
+    /// "delete_tenant" for the tenant itself
+    #[prost(string, tag="4")]
+    pub action: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub book_id: ::prost::alloc::string::String,
+    /// unset for "create" and tenant events
+    #[prost(message, optional, tag="6")]
+    pub before: ::core::option::Option<Book>,
+    /// unset for "delete" and tenant events
+    #[prost(message, optional, tag="7")]
+    pub after: ::core::option::Option<Book>,
+    /// the tenant whose catalogue was written
+    #[prost(string, tag="8")]
+    pub tenant: ::prost::alloc::string::String,
+    /// the `x-user` request metadata: who the client says it acts for, which
+    /// the server does not check
+    #[prost(string, tag="9")]
+    pub claimed_user: ::prost::alloc::string::String,
+    /// the `id` of the tenant when it was written, which tells apart tenants
+    /// created again under the same name
+    #[prost(uint64, tag="10")]
+    pub tenant_id: u64,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListAuditEventsRequest {
+    /// events of every book when empty
+    #[prost(string, tag="1")]
+    pub book_id: ::prost::alloc::string::String,
+    /// inclusive, unbounded when unset
+    #[prost(message, optional, tag="2")]
+    #[serde(with = "crate::json::timestamp")]
+    pub start_time: ::core::option::Option<::prost_types::Timestamp>,
+    /// exclusive, unbounded when unset
+    #[prost(message, optional, tag="3")]
+    #[serde(with = "crate::json::timestamp")]
+    pub end_time: ::core::option::Option<::prost_types::Timestamp>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListAuditEventsResponse {
+    /// oldest first
+    #[prost(message, repeated, tag="1")]
+    pub events: ::prost::alloc::vec::Vec<AuditEvent>,
+}
+/// An isolated catalogue, selected by the `x-tenant` request metadata. Requests
+/// without it use the "default" tenant, which always exists.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Tenant {
+    /// lowercase letters, digits and dashes
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    /// maximum number of books in the catalogue, 0 for no limit
+    #[prost(uint32, tag="2")]
+    pub max_books: u32,
+    /// set by the server
+    #[prost(uint32, tag="3")]
+    pub book_count: u32,
+    /// set by the server: the sequence of the tenant's "create_tenant" audit
+    /// event, 0 for the default tenant. Etags and audit events are scoped by it
+    #[prost(uint64, tag="4")]
+    pub id: u64,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateTenantRequest {
+    #[prost(message, optional, tag="1")]
+    pub tenant: ::core::option::Option<Tenant>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteTenantRequest {
+    /// the tenant's books are deleted with it, its audit events are kept
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListTenantsRequest {
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListTenantsResponse {
+    /// sorted by name
+    #[prost(message, repeated, tag="1")]
+    pub tenants: ::prost::alloc::vec::Vec<Tenant>,
 }
 /// Generated client implementations.
 pub mod book_store_client {
@@ -81,7 +279,7 @@ pub mod book_store_client {
         pub async fn get_book(
             &mut self,
             request: impl tonic::IntoRequest<super::GetBookRequest>,
-        ) -> Result<tonic::Response<super::GetBookResponse>, tonic::Status> {
+        ) -> Result<tonic::Response<super::Book>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -97,6 +295,179 @@ pub mod book_store_client {
             );
             self.inner.unary(request.into_request(), path, codec).await
         }
+        pub async fn list_books(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListBooksRequest>,
+        ) -> Result<tonic::Response<super::ListBooksResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/bookstore.BookStore/ListBooks",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn create_book(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateBookRequest>,
+        ) -> Result<tonic::Response<super::Book>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/bookstore.BookStore/CreateBook",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn update_book(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateBookRequest>,
+        ) -> Result<tonic::Response<super::Book>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/bookstore.BookStore/UpdateBook",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn delete_book(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteBookRequest>,
+        ) -> Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/bookstore.BookStore/DeleteBook",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn search_books(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SearchBooksRequest>,
+        ) -> Result<tonic::Response<super::SearchBooksResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/bookstore.BookStore/SearchBooks",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn list_audit_events(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListAuditEventsRequest>,
+        ) -> Result<tonic::Response<super::ListAuditEventsResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/bookstore.BookStore/ListAuditEvents",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// Admin calls, which need the `x-admin-token` metadata when the server has
+        /// an admin token configured.
+        pub async fn create_tenant(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateTenantRequest>,
+        ) -> Result<tonic::Response<super::Tenant>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/bookstore.BookStore/CreateTenant",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn delete_tenant(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteTenantRequest>,
+        ) -> Result<tonic::Response<()>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/bookstore.BookStore/DeleteTenant",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn list_tenants(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListTenantsRequest>,
+        ) -> Result<tonic::Response<super::ListTenantsResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/bookstore.BookStore/ListTenants",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -109,13 +480,51 @@ pub mod book_store_server {
         async fn get_book(
             &self,
             request: tonic::Request<super::GetBookRequest>,
-        ) -> Result<tonic::Response<super::GetBookResponse>, tonic::Status>;
+        ) -> Result<tonic::Response<super::Book>, tonic::Status>;
+        async fn list_books(
+            &self,
+            request: tonic::Request<super::ListBooksRequest>,
+        ) -> Result<tonic::Response<super::ListBooksResponse>, tonic::Status>;
+        async fn create_book(
+            &self,
+            request: tonic::Request<super::CreateBookRequest>,
+        ) -> Result<tonic::Response<super::Book>, tonic::Status>;
+        async fn update_book(
+            &self,
+            request: tonic::Request<super::UpdateBookRequest>,
+        ) -> Result<tonic::Response<super::Book>, tonic::Status>;
+        async fn delete_book(
+            &self,
+            request: tonic::Request<super::DeleteBookRequest>,
+        ) -> Result<tonic::Response<()>, tonic::Status>;
+        async fn search_books(
+            &self,
+            request: tonic::Request<super::SearchBooksRequest>,
+        ) -> Result<tonic::Response<super::SearchBooksResponse>, tonic::Status>;
+        async fn list_audit_events(
+            &self,
+            request: tonic::Request<super::ListAuditEventsRequest>,
+        ) -> Result<tonic::Response<super::ListAuditEventsResponse>, tonic::Status>;
+        /// Admin calls, which need the `x-admin-token` metadata when the server has
+        /// an admin token configured.
+        async fn create_tenant(
+            &self,
+            request: tonic::Request<super::CreateTenantRequest>,
+        ) -> Result<tonic::Response<super::Tenant>, tonic::Status>;
+        async fn delete_tenant(
+            &self,
+            request: tonic::Request<super::DeleteTenantRequest>,
+        ) -> Result<tonic::Response<()>, tonic::Status>;
+        async fn list_tenants(
+            &self,
+            request: tonic::Request<super::ListTenantsRequest>,
+        ) -> Result<tonic::Response<super::ListTenantsResponse>, tonic::Status>;
     }
     #[derive(Debug)]
     pub struct BookStoreServer<T: BookStore> {
         inner: _Inner<T>,
-        accept_compression_encodings: (),
-        send_compression_encodings: (),
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
     }
     struct _Inner<T>(Arc<T>);
     impl<T: BookStore> BookStoreServer<T> {
@@ -139,6 +548,18 @@ pub mod book_store_server {
         {
             InterceptedService::new(Self::new(inner), interceptor)
         }
+        /// Enable decompressing requests with `gzip`.
+        #[must_use]
+        pub fn accept_gzip(mut self) -> Self {
+            self.accept_compression_encodings.enable_gzip();
+            self
+        }
+        /// Compress responses with `gzip`, if the client supports it.
+        #[must_use]
+        pub fn send_gzip(mut self) -> Self {
+            self.send_compression_encodings.enable_gzip();
+            self
+        }
     }
     impl<T, B> tonic::codegen::Service<http::Request<B>> for BookStoreServer<T>
     where
@@ -163,7 +584,7 @@ pub mod book_store_server {
                     struct GetBookSvc<T: BookStore>(pub Arc<T>);
                     impl<T: BookStore> tonic::server::UnaryService<super::GetBookRequest>
                     for GetBookSvc<T> {
-                        type Response = super::GetBookResponse;
+                        type Response = super::Book;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
@@ -194,6 +615,358 @@ pub mod book_store_server {
                     };
                     Box::pin(fut)
                 }
+                "/bookstore.BookStore/ListBooks" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListBooksSvc<T: BookStore>(pub Arc<T>);
+                    impl<
+                        T: BookStore,
+                    > tonic::server::UnaryService<super::ListBooksRequest>
+                    for ListBooksSvc<T> {
+                        type Response = super::ListBooksResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListBooksRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).list_books(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListBooksSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bookstore.BookStore/CreateBook" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateBookSvc<T: BookStore>(pub Arc<T>);
+                    impl<
+                        T: BookStore,
+                    > tonic::server::UnaryService<super::CreateBookRequest>
+                    for CreateBookSvc<T> {
+                        type Response = super::Book;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateBookRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).create_book(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CreateBookSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bookstore.BookStore/UpdateBook" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateBookSvc<T: BookStore>(pub Arc<T>);
+                    impl<
+                        T: BookStore,
+                    > tonic::server::UnaryService<super::UpdateBookRequest>
+                    for UpdateBookSvc<T> {
+                        type Response = super::Book;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateBookRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).update_book(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdateBookSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bookstore.BookStore/DeleteBook" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteBookSvc<T: BookStore>(pub Arc<T>);
+                    impl<
+                        T: BookStore,
+                    > tonic::server::UnaryService<super::DeleteBookRequest>
+                    for DeleteBookSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteBookRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).delete_book(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteBookSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bookstore.BookStore/SearchBooks" => {
+                    #[allow(non_camel_case_types)]
+                    struct SearchBooksSvc<T: BookStore>(pub Arc<T>);
+                    impl<
+                        T: BookStore,
+                    > tonic::server::UnaryService<super::SearchBooksRequest>
+                    for SearchBooksSvc<T> {
+                        type Response = super::SearchBooksResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SearchBooksRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).search_books(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SearchBooksSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bookstore.BookStore/ListAuditEvents" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListAuditEventsSvc<T: BookStore>(pub Arc<T>);
+                    impl<
+                        T: BookStore,
+                    > tonic::server::UnaryService<super::ListAuditEventsRequest>
+                    for ListAuditEventsSvc<T> {
+                        type Response = super::ListAuditEventsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListAuditEventsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).list_audit_events(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListAuditEventsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bookstore.BookStore/CreateTenant" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateTenantSvc<T: BookStore>(pub Arc<T>);
+                    impl<
+                        T: BookStore,
+                    > tonic::server::UnaryService<super::CreateTenantRequest>
+                    for CreateTenantSvc<T> {
+                        type Response = super::Tenant;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateTenantRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).create_tenant(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CreateTenantSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bookstore.BookStore/DeleteTenant" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteTenantSvc<T: BookStore>(pub Arc<T>);
+                    impl<
+                        T: BookStore,
+                    > tonic::server::UnaryService<super::DeleteTenantRequest>
+                    for DeleteTenantSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteTenantRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).delete_tenant(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteTenantSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/bookstore.BookStore/ListTenants" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListTenantsSvc<T: BookStore>(pub Arc<T>);
+                    impl<
+                        T: BookStore,
+                    > tonic::server::UnaryService<super::ListTenantsRequest>
+                    for ListTenantsSvc<T> {
+                        type Response = super::ListTenantsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListTenantsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).list_tenants(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListTenantsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+
+use crate::bookstore::Book;
+use crate::search::{SearchHit, SearchIndex};
+
+/// Books keyed by id, together with the search index kept in sync with them.
+#[derive(Debug, Default)]
+pub(crate) struct Catalogue {
+    books: BTreeMap<String, Book>,
+    index: SearchIndex,
+    /// Bumped on every write, so an etag is never reused, not even after a
+    /// book is deleted and created again.
+    revision: u64,
+    /// Prefixes the etags, so that a tenant created again under the same
+    /// name does not hand out the etags of the one before.
+    seed: u64,
+}
+
+impl Catalogue {
+    pub fn new(books: Vec<Book>) -> Self {
+        let mut catalogue = Catalogue::default();
+        for mut book in books {
+            catalogue.stamp(&mut book);
+            catalogue.insert(book);
+        }
+        catalogue
+    }
+
+    /// An empty catalogue whose etags start with `seed`.
+    pub fn seeded(seed: u64) -> Self {
+        Catalogue {
+            seed,
+            ..Catalogue::default()
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Book> {
+        self.books.get(id)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.books.contains_key(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.books.len()
+    }
+
+    pub fn books(&self) -> impl Iterator<Item = &Book> {
+        self.books.values()
+    }
+
+    /// Gives a book about to be written a new etag.
+    pub fn stamp(&mut self, book: &mut Book) {
+        self.revision += 1;
+        book.etag = format!("{}-{}", self.seed, self.revision);
+    }
+
+    /// Adds or replaces a book.
+    pub fn insert(&mut self, book: Book) {
+        if let Some(previous) = self.books.remove(&book.id) {
+            self.index.remove(&previous);
+        }
+        self.index.add(&book);
+        self.books.insert(book.id.clone(), book);
+    }
+
+    pub fn remove(&mut self, id: &str) -> Option<Book> {
+        let book = self.books.remove(id)?;
+        self.index.remove(&book);
+        Some(book)
+    }
+
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        self.index.search(query, limit)
+    }
+}
@@ -0,0 +1,359 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::time::{self, Instant};
+use tonic::codegen::StdError;
+use tonic::metadata::{MetadataMap, MetadataValue};
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Code, Request, Response, Status};
+
+use crate::bookstore::book_store_client::BookStoreClient;
+use crate::bookstore::{
+    Book, CreateBookRequest, CreateTenantRequest, DeleteBookRequest, DeleteTenantRequest,
+    GetBookRequest, ListAuditEventsRequest, ListAuditEventsResponse, ListBooksRequest,
+    ListBooksResponse, ListTenantsRequest, ListTenantsResponse, SearchBooksRequest,
+    SearchBooksResponse, Tenant, UpdateBookRequest,
+};
+use crate::tenant::{ADMIN_TOKEN_METADATA, TENANT_METADATA};
+use crate::Compression;
+
+/// Exponential backoff between attempts of a call that failed with
+/// `UNAVAILABLE`. Only calls that cannot write twice are retried: every call
+/// but `create_book` for a book without an id.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Attempts including the first one, 1 disables retries.
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: f64,
+}
+
+impl RetryPolicy {
+    /// Checks the backoff never shrinks.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.multiplier >= 1.0 && self.multiplier.is_finite()) {
+            return Err(format!(
+                "the backoff multiplier must be at least 1, not {}",
+                self.multiplier
+            ));
+        }
+        Ok(())
+    }
+
+    /// Time to wait after the `attempt`th attempt (starting at 1) failed.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = i32::try_from(attempt.saturating_sub(1)).unwrap_or(i32::MAX);
+        let factor = self.multiplier.powi(exponent);
+        // computed in seconds, the product may not fit a `Duration`
+        let backoff = self.initial_backoff.as_secs_f64() * factor;
+        if backoff < self.max_backoff.as_secs_f64() {
+            Duration::from_secs_f64(backoff)
+        } else {
+            self.max_backoff
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+            multiplier: 2.0,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ClientOptions {
+    pub compression: Compression,
+    /// Deadline for a whole call, retries included.
+    pub timeout: Option<Duration>,
+    pub retry: RetryPolicy,
+    /// Sent as `x-tenant` with every call of a `BalancedClient`, the default
+    /// tenant is used without it.
+    pub tenant: Option<String>,
+    /// Sent as `x-admin-token` with every call of a `BalancedClient`.
+    pub admin_token: Option<String>,
+}
+
+impl ClientOptions {
+    pub fn apply(&self, mut client: BookStoreClient<Channel>) -> BookStoreClient<Channel> {
+        if self.compression.accept_gzip {
+            client = client.accept_gzip();
+        }
+        if self.compression.send_gzip {
+            client = client.send_gzip();
+        }
+        client
+    }
+
+    fn metadata(&self) -> Result<MetadataMap, String> {
+        let mut metadata = MetadataMap::new();
+        let entries = [
+            (TENANT_METADATA, &self.tenant),
+            (ADMIN_TOKEN_METADATA, &self.admin_token),
+        ];
+        for (key, value) in entries {
+            if let Some(value) = value {
+                let value = MetadataValue::try_from(value.as_str())
+                    .map_err(|_| format!("invalid {} metadata", key))?;
+                metadata.insert(key, value);
+            }
+        }
+        Ok(metadata)
+    }
+}
+
+/// A plain client to a single server. Only `options.compression` applies to
+/// it: use a [`BalancedClient`] for timeouts, retries, tenants and admin
+/// tokens.
+pub async fn connect<D>(
+    dst: D,
+    options: &ClientOptions,
+) -> Result<BookStoreClient<Channel>, tonic::transport::Error>
+where
+    D: TryInto<Endpoint>,
+    D::Error: Into<StdError>,
+{
+    let client = BookStoreClient::connect(dst).await?;
+    Ok(options.apply(client))
+}
+
+/// A `BookStore` client spreading calls round-robin over several servers,
+/// retrying `UNAVAILABLE` failures on the next server with exponential
+/// backoff, within an optional per-call deadline.
+#[derive(Debug, Clone)]
+pub struct BalancedClient {
+    clients: Arc<Vec<BookStoreClient<Channel>>>,
+    next: Arc<AtomicUsize>,
+    options: ClientOptions,
+}
+
+impl BalancedClient {
+    /// Connections are opened lazily, so servers that are down when the
+    /// client is created only fail the calls routed to them. Fails without
+    /// endpoints, on an invalid one or an invalid retry policy.
+    pub fn new<D>(endpoints: Vec<D>, options: ClientOptions) -> Result<Self, StdError>
+    where
+        D: TryInto<Endpoint>,
+        D::Error: Into<StdError>,
+    {
+        if endpoints.is_empty() {
+            return Err("at least one endpoint is required".into());
+        }
+        options.retry.validate()?;
+        let clients = endpoints
+            .into_iter()
+            .map(|dst| {
+                let channel = Endpoint::new(dst)?.connect_lazy();
+                Ok(options.apply(BookStoreClient::new(channel)))
+            })
+            .collect::<Result<_, tonic::transport::Error>>()?;
+        Ok(BalancedClient {
+            clients: Arc::new(clients),
+            next: Arc::new(AtomicUsize::new(0)),
+            options,
+        })
+    }
+
+    /// A client sharing the same connections and rotation whose calls must
+    /// finish within `timeout`.
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        let mut client = self.clone();
+        client.options.timeout = Some(timeout);
+        client
+    }
+
+    /// A client sharing the same connections and rotation whose calls work on
+    /// the catalogue of `tenant`.
+    pub fn with_tenant(&self, tenant: impl Into<String>) -> Self {
+        let mut client = self.clone();
+        client.options.tenant = Some(tenant.into());
+        client
+    }
+
+    fn next_client(&self) -> BookStoreClient<Channel> {
+        let next = self.next.fetch_add(1, Ordering::Relaxed);
+        self.clients[next % self.clients.len()].clone()
+    }
+
+    /// Sends `message` with `method`, retrying `UNAVAILABLE` failures if the
+    /// call is `idempotent`.
+    async fn call<M, R, F, Fut>(&self, message: M, idempotent: bool, method: F) -> Result<R, Status>
+    where
+        M: Clone,
+        F: Fn(BookStoreClient<Channel>, Request<M>) -> Fut,
+        Fut: Future<Output = Result<Response<R>, Status>>,
+    {
+        let deadline = self.options.timeout.map(|timeout| Instant::now() + timeout);
+        let deadline_exceeded = || Status::deadline_exceeded("deadline exceeded");
+        let metadata = self.options.metadata().map_err(Status::invalid_argument)?;
+        let mut attempt = 1;
+        loop {
+            let mut request = Request::new(message.clone());
+            *request.metadata_mut() = metadata.clone();
+            let result = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    // lets the server give up on the call as well
+                    request.set_timeout(remaining);
+                    match time::timeout_at(deadline, method(self.next_client(), request)).await {
+                        // the channel reports its own expired timeout as `CANCELLED`
+                        Ok(Err(_)) if Instant::now() >= deadline => return Err(deadline_exceeded()),
+                        Ok(result) => result,
+                        Err(_) => return Err(deadline_exceeded()),
+                    }
+                }
+                None => method(self.next_client(), request).await,
+            };
+
+            match result {
+                Err(status)
+                    if status.code() == Code::Unavailable
+                        && idempotent
+                        && attempt < self.options.retry.max_attempts =>
+                {
+                    let backoff = self.options.retry.backoff(attempt);
+                    if let Some(deadline) = deadline {
+                        if Instant::now() + backoff >= deadline {
+                            return Err(deadline_exceeded());
+                        }
+                    }
+                    time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                result => return result.map(Response::into_inner),
+            }
+        }
+    }
+
+    pub async fn get_book(&self, request: GetBookRequest) -> Result<Book, Status> {
+        self.call(request, true, |mut client, request| async move {
+            client.get_book(request).await
+        })
+        .await
+    }
+
+    pub async fn list_books(&self, request: ListBooksRequest) -> Result<ListBooksResponse, Status> {
+        self.call(request, true, |mut client, request| async move {
+            client.list_books(request).await
+        })
+        .await
+    }
+
+    /// Only retried for a book with an id, which cannot be created twice.
+    pub async fn create_book(&self, request: CreateBookRequest) -> Result<Book, Status> {
+        let has_id = request
+            .book
+            .as_ref()
+            .is_some_and(|book| !book.id.is_empty());
+        self.call(request, has_id, |mut client, request| async move {
+            client.create_book(request).await
+        })
+        .await
+    }
+
+    pub async fn update_book(&self, request: UpdateBookRequest) -> Result<Book, Status> {
+        self.call(request, true, |mut client, request| async move {
+            client.update_book(request).await
+        })
+        .await
+    }
+
+    pub async fn delete_book(&self, request: DeleteBookRequest) -> Result<(), Status> {
+        self.call(request, true, |mut client, request| async move {
+            client.delete_book(request).await
+        })
+        .await
+    }
+
+    pub async fn search_books(
+        &self,
+        request: SearchBooksRequest,
+    ) -> Result<SearchBooksResponse, Status> {
+        self.call(request, true, |mut client, request| async move {
+            client.search_books(request).await
+        })
+        .await
+    }
+
+    pub async fn list_audit_events(
+        &self,
+        request: ListAuditEventsRequest,
+    ) -> Result<ListAuditEventsResponse, Status> {
+        self.call(request, true, |mut client, request| async move {
+            client.list_audit_events(request).await
+        })
+        .await
+    }
+
+    pub async fn create_tenant(&self, request: CreateTenantRequest) -> Result<Tenant, Status> {
+        self.call(request, true, |mut client, request| async move {
+            client.create_tenant(request).await
+        })
+        .await
+    }
+
+    pub async fn delete_tenant(&self, request: DeleteTenantRequest) -> Result<(), Status> {
+        self.call(request, true, |mut client, request| async move {
+            client.delete_tenant(request).await
+        })
+        .await
+    }
+
+    pub async fn list_tenants(
+        &self,
+        request: ListTenantsRequest,
+    ) -> Result<ListTenantsResponse, Status> {
+        self.call(request, true, |mut client, request| async move {
+            client.list_tenants(request).await
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_never_overflows() {
+        let policy = RetryPolicy {
+            max_attempts: u32::MAX,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 1e10,
+        };
+        assert!(policy.validate().is_ok());
+        assert_eq!(policy.backoff(2), Duration::from_secs(30));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(30));
+
+        for multiplier in [0.5, -2.0, f64::NAN, f64::INFINITY] {
+            let policy = RetryPolicy {
+                multiplier,
+                ..RetryPolicy::default()
+            };
+            assert!(policy.validate().is_err(), "{}", multiplier);
+        }
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_up_to_the_cap() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+            multiplier: 2.0,
+        };
+        let backoffs: Vec<_> = (1..=5).map(|attempt| policy.backoff(attempt)).collect();
+        assert_eq!(
+            backoffs,
+            [100, 200, 400, 500, 500].map(Duration::from_millis)
+        );
+    }
+}
@@ -0,0 +1,21 @@
+/// Which directions of a connection use gzip compressed messages.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Compression {
+    /// Compress outgoing messages. The peer must accept gzip, otherwise the
+    /// call fails with `UNIMPLEMENTED`.
+    pub send_gzip: bool,
+    /// Decompress incoming messages and advertise gzip support to the peer.
+    pub accept_gzip: bool,
+}
+
+impl Compression {
+    pub const NONE: Compression = Compression {
+        send_gzip: false,
+        accept_gzip: false,
+    };
+
+    pub const GZIP: Compression = Compression {
+        send_gzip: true,
+        accept_gzip: true,
+    };
+}
@@ -0,0 +1,406 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::async_trait;
+use axum::body::Body;
+use axum::extract::{ConnectInfo, Extension, FromRequest, Path, Query, RequestParts};
+use axum::http::header::{self, HeaderName};
+use axum::http::{self, HeaderMap, HeaderValue, Method, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get};
+use axum::{Json, Router};
+use serde_json::json;
+use tonic::metadata::MetadataValue;
+use tonic::{Code, Request, Status};
+use tower_http::cors::CorsLayer;
+
+use crate::audit::{Peer, USER_METADATA};
+use crate::bookstore::{
+    book_store_server::BookStore, Book, CreateBookRequest, CreateTenantRequest, DeleteBookRequest,
+    DeleteTenantRequest, GetBookRequest, ListAuditEventsRequest, ListBooksRequest,
+    ListTenantsRequest, SearchBooksRequest, Tenant, UpdateBookRequest,
+};
+use crate::limits::{self, Limiter, RETRY_AFTER};
+use crate::tenant::{ADMIN_TOKEN_METADATA, TENANT_METADATA};
+
+/// Headers passed on to the service as request metadata.
+const FORWARDED_HEADERS: &[&str] = &[USER_METADATA, TENANT_METADATA, ADMIN_TOKEN_METADATA];
+
+/// gRPC-Web settings for browser clients, use `grpc_web().enable(service)`.
+/// Native gRPC requests are passed through untouched.
+pub fn grpc_web() -> tonic_web::Config {
+    tonic_web::config().expose_headers(vec![RETRY_AFTER])
+}
+
+/// REST/JSON mapping of the `BookStore` service:
+///
+/// - `GET /v1/books` -> `ListBooks`
+/// - `POST /v1/books` -> `CreateBook`
+/// - `GET /v1/books/{id}` -> `GetBook`
+/// - `PUT /v1/books/{id}` -> `UpdateBook`
+/// - `DELETE /v1/books/{id}` -> `DeleteBook`
+/// - `GET /v1/search?query={query}&limit={limit}` -> `SearchBooks`
+/// - `GET /v1/audit-events?bookId={id}&startTime={rfc3339}&endTime={rfc3339}`
+///   -> `ListAuditEvents`
+/// - `GET /v1/tenants` -> `ListTenants`
+/// - `POST /v1/tenants` -> `CreateTenant`
+/// - `DELETE /v1/tenants/{name}` -> `DeleteTenant`
+///
+/// Single books are returned with an `ETag` header. Updates and deletes take
+/// the etag from an `If-Match` header, updates fall back to the `etag` field
+/// of the body. `If-Match: *` matches any version of the book, and a weak
+/// `W/"…"` etag the version it names. A missing etag fails with `428
+/// Precondition Required`, a stale one with `412 Precondition Failed`.
+///
+/// The `x-user`, `x-tenant` and `x-admin-token` headers are passed on as
+/// request metadata, and the peer address to the audit log.
+///
+/// Requests are held to the budgets of `limiter`, per peer when the router is
+/// served with `into_make_service_with_connect_info::<SocketAddr>`. Rejected
+/// ones get `429 Too Many Requests` with a `Retry-After` header.
+///
+/// Browsers may only call the gateway from `allowed_origins`, none when it is
+/// empty.
+pub fn router<T: BookStore>(
+    bookstore: Arc<T>,
+    limiter: Limiter,
+    allowed_origins: Vec<HeaderValue>,
+) -> Router {
+    Router::new()
+        .route("/v1/books", get(list_books::<T>).post(create_book::<T>))
+        .route(
+            "/v1/books/:id",
+            get(get_book::<T>)
+                .put(update_book::<T>)
+                .delete(delete_book::<T>),
+        )
+        .route("/v1/search", get(search_books::<T>))
+        .route("/v1/audit-events", get(list_audit_events::<T>))
+        .route(
+            "/v1/tenants",
+            get(list_tenants::<T>).post(create_tenant::<T>),
+        )
+        .route("/v1/tenants/:name", delete(delete_tenant::<T>))
+        .layer(Extension(bookstore))
+        .layer(middleware::from_fn(move |request, next| {
+            limit(limiter.clone(), request, next)
+        }))
+        .layer(cors(allowed_origins))
+}
+
+fn cors(allowed_origins: Vec<HeaderValue>) -> CorsLayer {
+    let forwarded = FORWARDED_HEADERS
+        .iter()
+        .map(|name| HeaderName::from_static(name));
+    CorsLayer::new()
+        .allow_origin(allowed_origins)
+        .allow_methods(vec![Method::GET, Method::POST, Method::PUT, Method::DELETE])
+        .allow_headers(
+            [header::CONTENT_TYPE, header::IF_MATCH]
+                .into_iter()
+                .chain(forwarded)
+                .collect::<Vec<_>>(),
+        )
+        .expose_headers(vec![header::ETAG, HeaderName::from_static(RETRY_AFTER)])
+}
+
+async fn limit(limiter: Limiter, request: http::Request<Body>, next: Next<Body>) -> Response {
+    match limiter.admit(limits::peer(&request)) {
+        Ok(permit) => {
+            let response = next.run(request).await;
+            drop(permit);
+            response
+        }
+        Err(rejection) => StatusError::from(rejection.status()).into_response(),
+    }
+}
+
+async fn get_book<T: BookStore>(
+    Extension(bookstore): Extension<Arc<T>>,
+    Path(id): Path<String>,
+    caller: Caller,
+) -> Result<Response, StatusError> {
+    let response = bookstore
+        .get_book(caller.request(GetBookRequest { id }))
+        .await?;
+    Ok(book_response(StatusCode::OK, response.into_inner()))
+}
+
+async fn list_books<T: BookStore>(
+    Extension(bookstore): Extension<Arc<T>>,
+    caller: Caller,
+) -> Result<Response, StatusError> {
+    let response = bookstore
+        .list_books(caller.request(ListBooksRequest {}))
+        .await?;
+    Ok(Json(response.into_inner()).into_response())
+}
+
+async fn create_book<T: BookStore>(
+    Extension(bookstore): Extension<Arc<T>>,
+    caller: Caller,
+    Json(book): Json<Book>,
+) -> Result<Response, StatusError> {
+    let request = CreateBookRequest { book: Some(book) };
+    let response = bookstore.create_book(caller.request(request)).await?;
+    Ok(book_response(StatusCode::CREATED, response.into_inner()))
+}
+
+async fn update_book<T: BookStore>(
+    Extension(bookstore): Extension<Arc<T>>,
+    Path(id): Path<String>,
+    caller: Caller,
+    Json(book): Json<Book>,
+) -> Result<Response, StatusError> {
+    let etag = expected_etag(&*bookstore, &caller, &id)
+        .await?
+        .unwrap_or(book.etag);
+    let request = UpdateBookRequest {
+        book: Some(Book { id, etag, ..book }),
+    };
+    let response = bookstore
+        .update_book(caller.request(request))
+        .await
+        .map_err(StatusError::precondition)?;
+    Ok(book_response(StatusCode::OK, response.into_inner()))
+}
+
+async fn delete_book<T: BookStore>(
+    Extension(bookstore): Extension<Arc<T>>,
+    Path(id): Path<String>,
+    caller: Caller,
+) -> Result<Response, StatusError> {
+    let etag = expected_etag(&*bookstore, &caller, &id)
+        .await?
+        .unwrap_or_default();
+    bookstore
+        .delete_book(caller.request(DeleteBookRequest { id, etag }))
+        .await
+        .map_err(StatusError::precondition)?;
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn search_books<T: BookStore>(
+    Extension(bookstore): Extension<Arc<T>>,
+    Query(request): Query<SearchBooksRequest>,
+    caller: Caller,
+) -> Result<Response, StatusError> {
+    let response = bookstore.search_books(caller.request(request)).await?;
+    Ok(Json(response.into_inner()).into_response())
+}
+
+async fn list_audit_events<T: BookStore>(
+    Extension(bookstore): Extension<Arc<T>>,
+    Query(request): Query<ListAuditEventsRequest>,
+    caller: Caller,
+) -> Result<Response, StatusError> {
+    let response = bookstore.list_audit_events(caller.request(request)).await?;
+    Ok(Json(response.into_inner()).into_response())
+}
+
+async fn list_tenants<T: BookStore>(
+    Extension(bookstore): Extension<Arc<T>>,
+    caller: Caller,
+) -> Result<Response, StatusError> {
+    let response = bookstore
+        .list_tenants(caller.request(ListTenantsRequest {}))
+        .await?;
+    Ok(Json(response.into_inner()).into_response())
+}
+
+async fn create_tenant<T: BookStore>(
+    Extension(bookstore): Extension<Arc<T>>,
+    caller: Caller,
+    Json(tenant): Json<Tenant>,
+) -> Result<Response, StatusError> {
+    let request = CreateTenantRequest {
+        tenant: Some(tenant),
+    };
+    let response = bookstore.create_tenant(caller.request(request)).await?;
+    Ok((StatusCode::CREATED, Json(response.into_inner())).into_response())
+}
+
+async fn delete_tenant<T: BookStore>(
+    Extension(bookstore): Extension<Arc<T>>,
+    Path(name): Path<String>,
+    caller: Caller,
+) -> Result<Response, StatusError> {
+    bookstore
+        .delete_tenant(caller.request(DeleteTenantRequest { name }))
+        .await?;
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+/// The headers and peer address of a REST request, which the calls it maps
+/// to are sent with.
+struct Caller {
+    headers: HeaderMap,
+    peer: Option<SocketAddr>,
+}
+
+#[async_trait]
+impl<B: Send> FromRequest<B> for Caller {
+    type Rejection = Infallible;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let peer = req
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|info| info.0);
+        Ok(Caller {
+            headers: req.headers().clone(),
+            peer,
+        })
+    }
+}
+
+impl Caller {
+    fn request<T>(&self, message: T) -> Request<T> {
+        let mut request = Request::new(message);
+        for &name in FORWARDED_HEADERS {
+            let value = self
+                .headers
+                .get(name)
+                .and_then(|value| MetadataValue::try_from(value.as_bytes()).ok());
+            if let Some(value) = value {
+                request.metadata_mut().insert(name, value);
+            }
+        }
+        if let Some(peer) = self.peer {
+            request.extensions_mut().insert(Peer(peer));
+        }
+        request
+    }
+}
+
+fn book_response(status: StatusCode, book: Book) -> Response {
+    let etag = HeaderValue::from_str(&format!("\"{}\"", book.etag));
+    let mut response = (status, Json(book)).into_response();
+    if let Ok(etag) = etag {
+        response.headers_mut().insert(header::ETAG, etag);
+    }
+    response
+}
+
+#[derive(Debug, PartialEq)]
+enum IfMatch {
+    Any,
+    Etag(String),
+}
+
+fn if_match(headers: &HeaderMap) -> Option<IfMatch> {
+    let value = headers.get(header::IF_MATCH)?.to_str().ok()?.trim();
+    if value == "*" {
+        return Some(IfMatch::Any);
+    }
+    // every version of a book has a single representation, so a weak etag
+    // names the same version as the strong one
+    let value = value.strip_prefix("W/").unwrap_or(value);
+    Some(IfMatch::Etag(value.trim_matches('"').to_owned()))
+}
+
+/// The etag a write to book `id` must match according to `If-Match`, looking
+/// the current one up for `*`.
+async fn expected_etag<T: BookStore>(
+    bookstore: &T,
+    caller: &Caller,
+    id: &str,
+) -> Result<Option<String>, StatusError> {
+    match if_match(&caller.headers) {
+        None => Ok(None),
+        Some(IfMatch::Etag(etag)) => Ok(Some(etag)),
+        Some(IfMatch::Any) => {
+            let request = GetBookRequest { id: id.to_owned() };
+            let book = bookstore.get_book(caller.request(request)).await?;
+            Ok(Some(book.into_inner().etag))
+        }
+    }
+}
+
+/// A gRPC status rendered as an HTTP error with a JSON body.
+struct StatusError {
+    status: Status,
+    http: StatusCode,
+}
+
+impl StatusError {
+    /// The failure of a write guarded by `If-Match`, where a missing etag
+    /// fails its precondition and a stale one does not meet it.
+    fn precondition(status: Status) -> Self {
+        let http = match status.code() {
+            Code::FailedPrecondition => StatusCode::PRECONDITION_REQUIRED,
+            Code::Aborted => StatusCode::PRECONDITION_FAILED,
+            code => http_status(code),
+        };
+        StatusError { status, http }
+    }
+}
+
+impl From<Status> for StatusError {
+    fn from(status: Status) -> Self {
+        let http = http_status(status.code());
+        StatusError { status, http }
+    }
+}
+
+impl IntoResponse for StatusError {
+    fn into_response(self) -> Response {
+        let status = self.status;
+        let body = json!({
+            "code": status.code() as i32,
+            "message": status.message(),
+        });
+        let mut response = (self.http, Json(body)).into_response();
+        if let Some(retry_after) = status.metadata().get(RETRY_AFTER) {
+            if let Ok(retry_after) = HeaderValue::from_bytes(retry_after.as_bytes()) {
+                response
+                    .headers_mut()
+                    .insert(header::RETRY_AFTER, retry_after);
+            }
+        }
+        response
+    }
+}
+
+/// Maps gRPC codes to HTTP statuses the same way grpc-gateway does.
+fn http_status(code: Code) -> StatusCode {
+    match code {
+        Code::Ok => StatusCode::OK,
+        Code::Cancelled => StatusCode::from_u16(499).unwrap(),
+        Code::InvalidArgument | Code::FailedPrecondition | Code::OutOfRange => {
+            StatusCode::BAD_REQUEST
+        }
+        Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+        Code::NotFound => StatusCode::NOT_FOUND,
+        Code::AlreadyExists | Code::Aborted => StatusCode::CONFLICT,
+        Code::PermissionDenied => StatusCode::FORBIDDEN,
+        Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+        Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+        Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        Code::Unknown | Code::Internal | Code::DataLoss => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(if_match: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, if_match.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn if_match_accepts_any_strong_and_weak_etags() {
+        let etag = |etag: &str| Some(IfMatch::Etag(etag.to_owned()));
+        assert_eq!(if_match(&HeaderMap::new()), None);
+        assert_eq!(if_match(&headers("*")), Some(IfMatch::Any));
+        assert_eq!(if_match(&headers("\"3\"")), etag("3"));
+        assert_eq!(if_match(&headers(" W/\"3\" ")), etag("3"));
+        assert_eq!(if_match(&headers("3")), etag("3"));
+    }
+}
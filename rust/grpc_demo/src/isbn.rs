@@ -0,0 +1,63 @@
+/// Strips hyphens and spaces from `isbn` and checks it is a valid ISBN-10 or
+/// ISBN-13, returning the normalized form.
+pub fn normalize(isbn: &str) -> Option<String> {
+    let isbn: String = isbn
+        .chars()
+        .filter(|c| *c != '-' && *c != ' ')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let valid = match isbn.len() {
+        10 => is_valid_isbn10(&isbn),
+        13 => is_valid_isbn13(&isbn),
+        _ => false,
+    };
+    valid.then_some(isbn)
+}
+
+fn is_valid_isbn10(isbn: &str) -> bool {
+    let mut sum = 0;
+    for (i, c) in isbn.chars().enumerate() {
+        let digit = match c {
+            'X' if i == 9 => 10,
+            _ => match c.to_digit(10) {
+                Some(digit) => digit,
+                None => return false,
+            },
+        };
+        sum += (10 - i as u32) * digit;
+    }
+    sum % 11 == 0
+}
+
+fn is_valid_isbn13(isbn: &str) -> bool {
+    let mut sum = 0;
+    for (i, c) in isbn.chars().enumerate() {
+        let digit = match c.to_digit(10) {
+            Some(digit) => digit,
+            None => return false,
+        };
+        sum += if i % 2 == 0 { digit } else { digit * 3 };
+    }
+    sum % 10 == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_valid_isbns() {
+        assert_eq!(normalize("978-0-8041-3929-8").unwrap(), "9780804139298");
+        assert_eq!(normalize("0-8044-2957-x").unwrap(), "080442957X");
+        assert_eq!(normalize("1593278284").unwrap(), "1593278284");
+    }
+
+    #[test]
+    fn rejects_bad_checksums_and_shapes() {
+        assert_eq!(normalize("978-0-8041-3929-7"), None);
+        assert_eq!(normalize("1593278285"), None);
+        assert_eq!(normalize("X593278284"), None);
+        assert_eq!(normalize("97808041392"), None);
+        assert_eq!(normalize(""), None);
+    }
+}
@@ -0,0 +1,67 @@
+/// `google.protobuf.Timestamp` fields are rendered as RFC 3339 strings, as
+/// in the canonical protobuf JSON mapping.
+pub mod timestamp {
+    use chrono::{DateTime, SecondsFormat, Utc};
+    use prost_types::Timestamp;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(timestamp: &Option<Timestamp>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match timestamp {
+            Some(timestamp) => {
+                let time = DateTime::<Utc>::from_timestamp(
+                    timestamp.seconds,
+                    timestamp.nanos.max(0) as u32,
+                )
+                .ok_or_else(|| serde::ser::Error::custom("timestamp out of range"))?;
+                serializer.serialize_str(&time.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Timestamp>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(text) => {
+                let time = DateTime::parse_from_rfc3339(&text).map_err(D::Error::custom)?;
+                Ok(Some(Timestamp {
+                    seconds: time.timestamp(),
+                    nanos: time.timestamp_subsec_nanos() as i32,
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prost_types::Timestamp;
+    use serde_json::json;
+
+    use crate::bookstore::Book;
+
+    #[test]
+    fn timestamps_round_trip_as_rfc3339() {
+        let book = Book {
+            id: "1".to_owned(),
+            created_at: Some(Timestamp {
+                seconds: 1_654_586_348,
+                nanos: 500_000_000,
+            }),
+            ..Book::default()
+        };
+
+        let value = serde_json::to_value(&book).unwrap();
+        assert_eq!(value["createdAt"], json!("2022-06-07T07:19:08.500Z"));
+        assert_eq!(value["updatedAt"], json!(null));
+
+        let parsed: Book = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, book);
+    }
+}
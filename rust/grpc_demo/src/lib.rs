@@ -0,0 +1,26 @@
+pub mod bookstore {
+    include!("bookstore.rs");
+
+    pub const FILE_DESCRIPTOR_SET: &[u8] =
+        tonic::include_file_descriptor_set!("greeter_descriptor");
+}
+
+mod audit;
+mod book;
+mod catalogue;
+mod client;
+mod compression;
+pub mod gateway;
+mod isbn;
+mod json;
+mod limits;
+mod search;
+mod server;
+mod tenant;
+
+pub use audit::{AuditLog, USER_METADATA};
+pub use client::{connect, BalancedClient, ClientOptions, RetryPolicy};
+pub use compression::Compression;
+pub use limits::{Limited, Limiter, Limits, RateLimit, RETRY_AFTER};
+pub use server::{BookStoreImpl, ServerOptions};
+pub use tenant::{ADMIN_TOKEN_METADATA, DEFAULT_TENANT, TENANT_METADATA};
@@ -0,0 +1,414 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use axum::extract::ConnectInfo;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tonic::codegen::{http, BoxFuture, Service};
+use tonic::metadata::MetadataMap;
+use tonic::transport::server::TcpConnectInfo;
+use tonic::transport::NamedService;
+use tonic::{body::BoxBody, Code, Status};
+
+/// Metadata key holding the number of seconds a rejected client should wait.
+pub const RETRY_AFTER: &str = "retry-after";
+
+/// Token bucket settings applied to every peer (keyed by IP address).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// Requests a peer may send back to back before being throttled.
+    pub burst: u32,
+    /// Tokens refilled per second.
+    pub per_second: f64,
+}
+
+impl RateLimit {
+    fn validate(&self) -> Result<(), String> {
+        if self.burst < 1 {
+            return Err("the rate limit burst must be at least 1".to_owned());
+        }
+        if !(self.per_second > 0.0 && self.per_second.is_finite()) {
+            return Err(format!(
+                "the rate limit must refill a positive number of tokens per second, not {}",
+                self.per_second
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Limits {
+    pub rate: Option<RateLimit>,
+    /// Requests served at the same time across all peers.
+    pub max_concurrent_requests: Option<usize>,
+}
+
+impl Limits {
+    /// Checks the limits let some requests through at a finite rate.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(rate) = &self.rate {
+            rate.validate()?;
+        }
+        if self.max_concurrent_requests == Some(0) {
+            return Err("at least one concurrent request must be allowed".to_owned());
+        }
+        Ok(())
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Peers whose bucket is full are forgotten once this many are tracked.
+const MAX_TRACKED_PEERS: usize = 1024;
+
+struct RateLimiter {
+    limit: RateLimit,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+impl RateLimiter {
+    fn new(limit: RateLimit) -> Self {
+        RateLimiter {
+            limit,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Takes a token for `peer`, or returns how long until one is available.
+    fn acquire(&self, peer: IpAddr, now: Instant) -> Result<(), Duration> {
+        let burst = self.limit.burst as f64;
+        let mut buckets = self.buckets.lock().unwrap();
+
+        if buckets.len() >= MAX_TRACKED_PEERS {
+            let rate = self.limit.per_second;
+            buckets.retain(|_, bucket| {
+                bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * rate < burst
+            });
+        }
+
+        let bucket = buckets.entry(peer).or_insert(Bucket {
+            tokens: burst,
+            updated: now,
+        });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.limit.per_second).min(burst);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let missing = 1.0 - bucket.tokens;
+            Err(Duration::from_secs_f64(missing / self.limit.per_second))
+        }
+    }
+}
+
+fn resource_exhausted(message: &str, retry_after: Duration) -> Status {
+    let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    let mut metadata = MetadataMap::new();
+    metadata.insert(RETRY_AFTER, secs.max(1).to_string().parse().unwrap());
+    Status::with_metadata(Code::ResourceExhausted, message, metadata)
+}
+
+/// The budgets enforcing some [`Limits`]. Clones share them, so that the
+/// gRPC service and the REST gateway hold a peer to the same limits.
+#[derive(Clone, Default)]
+pub struct Limiter {
+    rate: Option<Arc<RateLimiter>>,
+    concurrency: Option<Arc<Semaphore>>,
+}
+
+impl Limiter {
+    /// Fails if the limits do not pass [`Limits::validate`].
+    pub fn new(limits: &Limits) -> Result<Self, String> {
+        limits.validate()?;
+        Ok(Limiter {
+            rate: limits.rate.map(|limit| Arc::new(RateLimiter::new(limit))),
+            concurrency: limits
+                .max_concurrent_requests
+                .map(|max| Arc::new(Semaphore::new(max))),
+        })
+    }
+
+    /// Admits a request from `peer`, returning the permit to hold while it is
+    /// served. Requests without a known peer are only held to the
+    /// concurrency cap.
+    pub(crate) fn admit(
+        &self,
+        peer: Option<IpAddr>,
+    ) -> Result<Option<OwnedSemaphorePermit>, Rejection> {
+        // checked first, so that a request rejected here costs no rate token
+        let permit = match &self.concurrency {
+            Some(semaphore) => match semaphore.clone().try_acquire_owned() {
+                Ok(permit) => Some(permit),
+                Err(_) => {
+                    return Err(Rejection {
+                        message: "too many concurrent requests",
+                        retry_after: Duration::from_secs(1),
+                    })
+                }
+            },
+            None => None,
+        };
+
+        if let (Some(rate), Some(peer)) = (&self.rate, peer) {
+            if let Err(retry_after) = rate.acquire(peer, Instant::now()) {
+                return Err(Rejection {
+                    message: "rate limit exceeded",
+                    retry_after,
+                });
+            }
+        }
+        Ok(permit)
+    }
+}
+
+/// Why a [`Limiter`] turned a request away, and when to try again.
+#[derive(Debug)]
+pub(crate) struct Rejection {
+    message: &'static str,
+    retry_after: Duration,
+}
+
+impl Rejection {
+    /// The `RESOURCE_EXHAUSTED` status to answer with.
+    pub fn status(&self) -> Status {
+        resource_exhausted(self.message, self.retry_after)
+    }
+}
+
+/// The IP address a request came from, as set by tonic or by axum's
+/// `into_make_service_with_connect_info::<SocketAddr>`.
+pub(crate) fn peer<B>(req: &http::Request<B>) -> Option<IpAddr> {
+    let extensions = req.extensions();
+    let tonic = extensions
+        .get::<TcpConnectInfo>()
+        .and_then(|info| info.remote_addr());
+    let axum = extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|info| info.0);
+    tonic.or(axum).map(|addr| addr.ip())
+}
+
+/// Wraps a gRPC service, rejecting requests over the configured [`Limits`]
+/// with `RESOURCE_EXHAUSTED` before they reach the inner service.
+pub struct Limited<S> {
+    inner: S,
+    limiter: Limiter,
+}
+
+impl<S> Limited<S> {
+    /// Fails if the limits do not pass [`Limits::validate`].
+    pub fn new(inner: S, limits: &Limits) -> Result<Self, String> {
+        Ok(Limited::with_limiter(inner, Limiter::new(limits)?))
+    }
+
+    /// Limits `inner` with the budgets of `limiter`.
+    pub fn with_limiter(inner: S, limiter: Limiter) -> Self {
+        Limited { inner, limiter }
+    }
+
+    pub fn limiter(&self) -> &Limiter {
+        &self.limiter
+    }
+}
+
+impl<S: Clone> Clone for Limited<S> {
+    fn clone(&self) -> Self {
+        Limited {
+            inner: self.inner.clone(),
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+impl<S, B> Service<http::Request<B>> for Limited<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<BoxBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = http::Response<BoxBody>;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let permit = match self.limiter.admit(peer(&req)) {
+            Ok(permit) => permit,
+            Err(rejection) => {
+                let response = rejection.status().to_http();
+                return Box::pin(async move { Ok(response) });
+            }
+        };
+
+        let fut = self.inner.call(req);
+        Box::pin(async move {
+            let response = fut.await;
+            drop(permit);
+            response
+        })
+    }
+}
+
+impl<S: NamedService> NamedService for Limited<S> {
+    const NAME: &'static str = S::NAME;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_allows_burst_then_refills() {
+        let limiter = RateLimiter::new(RateLimit {
+            burst: 2,
+            per_second: 4.0,
+        });
+        let peer: IpAddr = "10.0.0.1".parse().unwrap();
+        let start = Instant::now();
+
+        assert!(limiter.acquire(peer, start).is_ok());
+        assert!(limiter.acquire(peer, start).is_ok());
+        assert_eq!(
+            limiter.acquire(peer, start),
+            Err(Duration::from_millis(250))
+        );
+        assert!(limiter
+            .acquire(peer, start + Duration::from_millis(250))
+            .is_ok());
+    }
+
+    #[test]
+    fn peers_have_separate_buckets() {
+        let limiter = RateLimiter::new(RateLimit {
+            burst: 1,
+            per_second: 1.0,
+        });
+        let now = Instant::now();
+
+        assert!(limiter.acquire("10.0.0.1".parse().unwrap(), now).is_ok());
+        assert!(limiter.acquire("10.0.0.1".parse().unwrap(), now).is_err());
+        assert!(limiter.acquire("10.0.0.2".parse().unwrap(), now).is_ok());
+    }
+
+    #[tokio::test]
+    async fn concurrency_cap_rejects_until_a_request_finishes() {
+        use tokio::sync::oneshot;
+        use tower::{service_fn, ServiceExt};
+
+        let (release, released) = oneshot::channel::<()>();
+        let released = Arc::new(Mutex::new(Some(released)));
+        let inner = service_fn(move |_: http::Request<()>| {
+            let released = released.lock().unwrap().take();
+            async move {
+                if let Some(released) = released {
+                    released.await.unwrap();
+                }
+                Ok::<_, std::convert::Infallible>(http::Response::new(tonic::body::empty_body()))
+            }
+        });
+        let limits = Limits {
+            max_concurrent_requests: Some(1),
+            ..Limits::default()
+        };
+        let service = Limited::new(inner, &limits).unwrap();
+
+        let pending = tokio::spawn(service.clone().oneshot(http::Request::new(())));
+        tokio::task::yield_now().await;
+
+        let rejected = service
+            .clone()
+            .oneshot(http::Request::new(()))
+            .await
+            .unwrap();
+        assert_eq!(rejected.headers()["grpc-status"], "8");
+        assert_eq!(rejected.headers()[RETRY_AFTER], "1");
+
+        release.send(()).unwrap();
+        pending.await.unwrap().unwrap();
+        let accepted = service.oneshot(http::Request::new(())).await.unwrap();
+        assert!(accepted.headers().get("grpc-status").is_none());
+    }
+
+    #[tokio::test]
+    async fn concurrency_rejections_cost_no_rate_token() {
+        use tokio::sync::oneshot;
+        use tower::{service_fn, ServiceExt};
+
+        let (release, released) = oneshot::channel::<()>();
+        let released = Arc::new(Mutex::new(Some(released)));
+        let inner = service_fn(move |_: http::Request<()>| {
+            let released = released.lock().unwrap().take();
+            async move {
+                if let Some(released) = released {
+                    released.await.unwrap();
+                }
+                Ok::<_, std::convert::Infallible>(http::Response::new(tonic::body::empty_body()))
+            }
+        });
+        let limits = Limits {
+            rate: Some(RateLimit {
+                burst: 2,
+                per_second: 0.001,
+            }),
+            max_concurrent_requests: Some(1),
+        };
+        let service = Limited::new(inner, &limits).unwrap();
+        let request = || {
+            let mut request = http::Request::new(());
+            let addr: SocketAddr = "10.0.0.1:1234".parse().unwrap();
+            request.extensions_mut().insert(ConnectInfo(addr));
+            request
+        };
+
+        let pending = tokio::spawn(service.clone().oneshot(request()));
+        tokio::task::yield_now().await;
+        let rejected = service.clone().oneshot(request()).await.unwrap();
+        assert_eq!(rejected.headers()["grpc-status"], "8");
+
+        release.send(()).unwrap();
+        pending.await.unwrap().unwrap();
+        let accepted = service.clone().oneshot(request()).await.unwrap();
+        assert!(accepted.headers().get("grpc-status").is_none());
+        let limited = service.oneshot(request()).await.unwrap();
+        assert_eq!(limited.headers()["grpc-status"], "8");
+    }
+
+    #[test]
+    fn limits_must_let_requests_through() {
+        let rate = |burst, per_second| Limits {
+            rate: Some(RateLimit { burst, per_second }),
+            max_concurrent_requests: None,
+        };
+        assert!(rate(1, 0.5).validate().is_ok());
+        assert!(rate(0, 1.0).validate().is_err());
+        assert!(rate(1, 0.0).validate().is_err());
+        assert!(rate(1, -1.0).validate().is_err());
+        assert!(rate(1, f64::NAN).validate().is_err());
+        assert!(rate(1, f64::INFINITY).validate().is_err());
+
+        let concurrency = Limits {
+            max_concurrent_requests: Some(0),
+            ..Limits::default()
+        };
+        assert!(Limited::new((), &concurrency).is_err());
+        assert!(Limited::new((), &Limits::default()).is_ok());
+    }
+
+    #[test]
+    fn retry_after_is_rounded_up_to_seconds() {
+        let status = resource_exhausted("slow down", Duration::from_millis(1500));
+        assert_eq!(status.code(), Code::ResourceExhausted);
+        assert_eq!(status.metadata().get(RETRY_AFTER).unwrap(), "2");
+    }
+}
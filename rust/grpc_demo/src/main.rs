@@ -1,50 +1,69 @@
-use bookstore::{book_store_server::BookStore, GetBookRequest, GetBookResponse};
-use tonic::{transport::Server, Request, Response, Status};
+use std::env;
+use std::net::SocketAddr;
+use std::sync::Arc;
 
-use crate::bookstore::book_store_server::BookStoreServer;
+use grpc_demo::gateway;
+use grpc_demo::{bookstore, AuditLog, BookStoreImpl, Limits, RateLimit, ServerOptions};
+use tonic::transport::Server;
 
-mod bookstore {
-    include!("bookstore.rs");
-
-    pub(crate) const FILE_DESCRIPTOR_SET: &[u8] =
-        tonic::include_file_descriptor_set!("greeter_descriptor");
-}
-
-#[derive(Default)]
-pub struct BookStoreImpl {}
-
-#[tonic::async_trait]
-impl BookStore for BookStoreImpl {
-    async fn get_book(
-        &self,
-        request: Request<GetBookRequest>,
-    ) -> Result<Response<GetBookResponse>, Status> {
-        println!("Request from {:?}", request.remote_addr());
-
-        let response = GetBookResponse {
-            id: request.into_inner().id,
-            author: "Peter".to_owned(),
-            name: "Zero to One".to_owned(),
-            year: 2014,
-        };
-        Ok(Response::new(response))
-    }
-}
+/// Token required by the tenant admin calls.
+const ADMIN_TOKEN_VAR: &str = "BOOKSTORE_ADMIN_TOKEN";
+/// Comma separated origins browsers may call the REST gateway from.
+const CORS_ORIGINS_VAR: &str = "BOOKSTORE_CORS_ORIGINS";
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let addr = "[::1]:50051".parse().unwrap();
-    let bookstore = BookStoreImpl::default();
+    let gateway_addr = "[::1]:8080".parse().unwrap();
+    let audit_log = AuditLog::open("audit.log")?;
+    let mut bookstore = BookStoreImpl::default().with_audit_log(audit_log);
+    match env::var(ADMIN_TOKEN_VAR) {
+        Ok(token) if !token.is_empty() => bookstore = bookstore.with_admin_token(token),
+        _ => println!("{} is not set, admin calls are disabled", ADMIN_TOKEN_VAR),
+    }
+    let allowed_origins = match env::var(CORS_ORIGINS_VAR) {
+        Ok(origins) => origins
+            .split(',')
+            .map(str::trim)
+            .filter(|origin| !origin.is_empty())
+            .map(str::parse)
+            .collect::<Result<_, _>>()?,
+        Err(_) => Vec::new(),
+    };
+    let bookstore = Arc::new(bookstore);
+    let options = ServerOptions {
+        limits: Limits {
+            rate: Some(RateLimit {
+                burst: 20,
+                per_second: 10.0,
+            }),
+            max_concurrent_requests: Some(128),
+        },
+        ..ServerOptions::default()
+    };
 
     let reflection_service = tonic_reflection::server::Builder::configure()
         .register_encoded_file_descriptor_set(bookstore::FILE_DESCRIPTOR_SET)
         .build()
         .unwrap();
 
+    // the gateway shares the budgets of the gRPC service
+    let service = bookstore.clone().into_service(&options)?;
+    let router = gateway::router(bookstore, service.limiter().clone(), allowed_origins);
+    let gateway = axum::Server::bind(&gateway_addr)
+        .serve(router.into_make_service_with_connect_info::<SocketAddr>());
+    tokio::spawn(async move {
+        if let Err(e) = gateway.await {
+            eprintln!("REST gateway error: {}", e);
+        }
+    });
+
     println!("Bookstore server listening on {}", addr);
+    println!("Bookstore REST gateway listening on {}", gateway_addr);
 
     Server::builder()
-        .add_service(BookStoreServer::new(bookstore))
+        .accept_http1(true)
+        .add_service(gateway::grpc_web().enable(service))
         .add_service(reflection_service)
         .serve(addr)
         .await?;
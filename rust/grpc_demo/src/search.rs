@@ -0,0 +1,185 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::bookstore::Book;
+
+const TITLE_WEIGHT: f64 = 2.0;
+const AUTHOR_WEIGHT: f64 = 1.0;
+/// Share of a term's weight awarded when the query token is only a prefix.
+const PREFIX_FACTOR: f64 = 0.5;
+
+/// Splits `text` into lowercase alphanumeric tokens.
+pub(crate) fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_lowercase)
+}
+
+fn weighted_tokens(book: &Book) -> impl Iterator<Item = (String, f64)> + '_ {
+    let title = tokenize(&book.title).map(|token| (token, TITLE_WEIGHT));
+    let authors = book
+        .authors
+        .iter()
+        .flat_map(|author| tokenize(author))
+        .map(|token| (token, AUTHOR_WEIGHT));
+    title.chain(authors)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SearchHit {
+    pub id: String,
+    pub score: f64,
+}
+
+/// Inverted index over book titles and authors.
+#[derive(Debug, Default)]
+pub(crate) struct SearchIndex {
+    /// term -> book id -> weight of the term in that book
+    postings: BTreeMap<String, HashMap<String, f64>>,
+}
+
+impl SearchIndex {
+    pub fn add(&mut self, book: &Book) {
+        for (token, weight) in weighted_tokens(book) {
+            *self
+                .postings
+                .entry(token)
+                .or_default()
+                .entry(book.id.clone())
+                .or_default() += weight;
+        }
+    }
+
+    pub fn remove(&mut self, book: &Book) {
+        for (token, _) in weighted_tokens(book) {
+            if let Some(books) = self.postings.get_mut(&token) {
+                books.remove(&book.id);
+                if books.is_empty() {
+                    self.postings.remove(&token);
+                }
+            }
+        }
+    }
+
+    /// Ranks books matching `query`. Books matching more of the query tokens
+    /// come first, then books with the higher score. A `limit` of 0 returns
+    /// every match.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let mut tokens: Vec<String> = tokenize(query).collect();
+        tokens.sort();
+        tokens.dedup();
+
+        let mut scores: HashMap<&str, (usize, f64)> = HashMap::new();
+        for token in &tokens {
+            let mut best: HashMap<&str, f64> = HashMap::new();
+            let matches = self
+                .postings
+                .range(token.clone()..)
+                .take_while(|(term, _)| term.starts_with(token.as_str()));
+            for (term, books) in matches {
+                let factor = if term == token { 1.0 } else { PREFIX_FACTOR };
+                for (id, weight) in books {
+                    let score = best.entry(id).or_default();
+                    *score = score.max(weight * factor);
+                }
+            }
+            for (id, score) in best {
+                let entry = scores.entry(id).or_default();
+                entry.0 += 1;
+                entry.1 += score;
+            }
+        }
+
+        let mut ranked: Vec<(usize, SearchHit)> = scores
+            .into_iter()
+            .map(|(id, (matched, score))| {
+                let hit = SearchHit {
+                    id: id.to_owned(),
+                    score,
+                };
+                (matched, hit)
+            })
+            .collect();
+        ranked.sort_by(|(matched_a, a), (matched_b, b)| {
+            matched_b
+                .cmp(matched_a)
+                .then(b.score.total_cmp(&a.score))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        if limit > 0 {
+            ranked.truncate(limit);
+        }
+        ranked.into_iter().map(|(_, hit)| hit).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book(id: &str, title: &str, authors: &[&str]) -> Book {
+        Book {
+            id: id.to_owned(),
+            title: title.to_owned(),
+            authors: authors.iter().map(|author| author.to_string()).collect(),
+            ..Book::default()
+        }
+    }
+
+    fn index() -> SearchIndex {
+        let mut index = SearchIndex::default();
+        index.add(&book(
+            "rust",
+            "The Rust Programming Language",
+            &["Steve Klabnik"],
+        ));
+        index.add(&book(
+            "async",
+            "Asynchronous Programming in Rust",
+            &["Carl Fredrik Samson"],
+        ));
+        index.add(&book("dune", "Dune", &["Frank Herbert"]));
+        index.add(&book("rusty", "Rusty Nails", &["Frank Rust"]));
+        index
+    }
+
+    fn ids(hits: Vec<SearchHit>) -> Vec<String> {
+        hits.into_iter().map(|hit| hit.id).collect()
+    }
+
+    #[test]
+    fn matching_is_case_insensitive() {
+        assert_eq!(ids(index().search("DUNE", 0)), ["dune"]);
+        assert_eq!(ids(index().search("herbert", 0)), ["dune"]);
+    }
+
+    #[test]
+    fn title_matches_outrank_author_and_prefix_matches() {
+        // exact title match, exact title match, author match, title prefix
+        assert_eq!(ids(index().search("rust", 0)), ["async", "rust", "rusty"]);
+        let hits = index().search("rust", 0);
+        assert!(hits[0].score > hits[2].score);
+    }
+
+    #[test]
+    fn books_matching_more_tokens_rank_first() {
+        assert_eq!(
+            ids(index().search("rust klabnik", 0)),
+            ["rust", "async", "rusty"]
+        );
+        assert_eq!(ids(index().search("rust klabnik", 1)), ["rust"]);
+    }
+
+    #[test]
+    fn prefixes_match() {
+        assert_eq!(ids(index().search("prog", 0)), ["async", "rust"]);
+        assert_eq!(ids(index().search("fr", 0)), ["async", "dune", "rusty"]);
+    }
+
+    #[test]
+    fn removed_books_are_not_found() {
+        let mut index = index();
+        index.remove(&book("dune", "Dune", &["Frank Herbert"]));
+        assert!(index.search("dune", 0).is_empty());
+        assert!(!index.postings.contains_key("dune"));
+    }
+}
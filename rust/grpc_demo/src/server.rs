@@ -0,0 +1,449 @@
+use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
+
+use prost_types::Timestamp;
+use tonic::metadata::MetadataMap;
+use tonic::{Request, Response, Status};
+
+use crate::audit::{self, AuditLog, Origin};
+use crate::book;
+use crate::bookstore::{
+    book_store_server::{BookStore, BookStoreServer},
+    AuditEvent, Book, CreateBookRequest, CreateTenantRequest, DeleteBookRequest,
+    DeleteTenantRequest, GetBookRequest, ListAuditEventsRequest, ListAuditEventsResponse,
+    ListBooksRequest, ListBooksResponse, ListTenantsRequest, ListTenantsResponse,
+    SearchBooksRequest, SearchBooksResponse, SearchResult, Tenant, UpdateBookRequest,
+};
+use crate::catalogue::Catalogue;
+use crate::limits::{Limited, Limits};
+use crate::tenant::{self, Space, Tenants, ADMIN_TOKEN_METADATA, DEFAULT_TENANT, TENANT_METADATA};
+use crate::Compression;
+
+#[derive(Debug, Clone)]
+pub struct ServerOptions {
+    pub compression: Compression,
+    pub limits: Limits,
+}
+
+impl Default for ServerOptions {
+    fn default() -> Self {
+        // responses are only compressed for clients that advertise gzip
+        ServerOptions {
+            compression: Compression::GZIP,
+            limits: Limits::default(),
+        }
+    }
+}
+
+pub struct BookStoreImpl {
+    tenants: RwLock<Tenants>,
+    next_id: AtomicU64,
+    audit: Mutex<AuditLog>,
+    admin_token: Option<String>,
+}
+
+impl BookStoreImpl {
+    /// Creates a store with `books` as the initial catalogue of the default
+    /// tenant, which is trusted as is. Writes are audited in memory only and
+    /// admin calls are refused until an admin token is set.
+    pub fn new(books: Vec<Book>) -> Self {
+        BookStoreImpl {
+            tenants: RwLock::new(Tenants::new(Catalogue::new(books))),
+            next_id: AtomicU64::new(1),
+            audit: Mutex::new(AuditLog::in_memory()),
+            admin_token: None,
+        }
+    }
+
+    pub fn with_audit_log(mut self, audit: AuditLog) -> Self {
+        self.audit = Mutex::new(audit);
+        self
+    }
+
+    /// Requires admin calls to send `token` as `x-admin-token` metadata.
+    pub fn with_admin_token(mut self, token: impl Into<String>) -> Self {
+        self.admin_token = Some(token.into());
+        self
+    }
+
+    /// Fails if `options` holds invalid limits.
+    pub fn into_service(
+        self: Arc<Self>,
+        options: &ServerOptions,
+    ) -> Result<Limited<BookStoreServer<Self>>, String> {
+        let mut service = BookStoreServer::from_arc(self);
+        if options.compression.accept_gzip {
+            service = service.accept_gzip();
+        }
+        if options.compression.send_gzip {
+            service = service.send_gzip();
+        }
+        Limited::new(service, &options.limits)
+    }
+
+    /// Appends a write to the audit log, before it is applied to the
+    /// catalogue so that no write goes unrecorded.
+    fn record(
+        &self,
+        tenant: &str,
+        space: &Space,
+        origin: Origin,
+        action: &str,
+        before: Option<&Book>,
+        after: Option<&Book>,
+    ) -> io::Result<()> {
+        let book_id = after
+            .or(before)
+            .map(|book| book.id.clone())
+            .unwrap_or_default();
+        let event = AuditEvent {
+            book_id,
+            before: before.cloned(),
+            after: after.cloned(),
+            ..event(tenant, space.id, origin, action)
+        };
+        self.audit.lock().unwrap().append(event)
+    }
+
+    /// The tenant `metadata` selects, along with its name.
+    fn space(&self, metadata: &MetadataMap) -> Result<(String, Arc<Space>), String> {
+        let name = match metadata.get(TENANT_METADATA) {
+            Some(name) => name
+                .to_str()
+                .map_err(|_| "invalid tenant name".to_owned())?,
+            None => DEFAULT_TENANT,
+        };
+        match self.tenants.read().unwrap().get(name) {
+            Some(space) => Ok((name.to_owned(), space)),
+            None => Err(format!("tenant {:?} not found", name)),
+        }
+    }
+
+    /// Checks the admin token of an admin call. Without a token of its own
+    /// the server refuses every admin call.
+    fn unauthorized(&self, metadata: &MetadataMap) -> Option<Status> {
+        let expected = match self.admin_token.as_deref() {
+            Some(expected) => expected,
+            None => {
+                return Some(Status::permission_denied(
+                    "admin calls are disabled, the server has no admin token",
+                ))
+            }
+        };
+        match metadata.get(ADMIN_TOKEN_METADATA) {
+            None => Some(Status::unauthenticated("an admin token is required")),
+            Some(token) if !tenant::token_matches(token.as_bytes(), expected.as_bytes()) => {
+                Some(Status::permission_denied("invalid admin token"))
+            }
+            Some(_) => None,
+        }
+    }
+
+    fn generate_id(&self, catalogue: &Catalogue) -> String {
+        loop {
+            let id = self.next_id.fetch_add(1, Ordering::Relaxed).to_string();
+            if !catalogue.contains(&id) {
+                return id;
+            }
+        }
+    }
+}
+
+impl Default for BookStoreImpl {
+    fn default() -> Self {
+        BookStoreImpl::new(vec![Book {
+            id: "zero-to-one".to_owned(),
+            title: "Zero to One".to_owned(),
+            authors: vec!["Peter Thiel".to_owned(), "Blake Masters".to_owned()],
+            isbn: "9780804139298".to_owned(),
+            tags: vec!["business".to_owned(), "startups".to_owned()],
+            publisher: "Crown Business".to_owned(),
+            year: 2014,
+            created_at: None,
+            updated_at: None,
+            etag: String::new(),
+        }])
+    }
+}
+
+/// An audit event without a book.
+fn event(tenant: &str, tenant_id: u64, origin: Origin, action: &str) -> AuditEvent {
+    AuditEvent {
+        time: Some(SystemTime::now().into()),
+        actor: origin.actor,
+        action: action.to_owned(),
+        tenant: tenant.to_owned(),
+        claimed_user: origin.claimed_user,
+        tenant_id,
+        ..AuditEvent::default()
+    }
+}
+
+fn audit_failed(error: io::Error) -> Status {
+    Status::internal(format!("failed to write the audit log: {}", error))
+}
+
+/// Checks the etag a client sent with a write against the stored book.
+fn etag_mismatch(current: &Book, etag: &str) -> Option<Status> {
+    if etag.is_empty() {
+        Some(Status::failed_precondition(format!(
+            "the etag of book {:?} is required",
+            current.id
+        )))
+    } else if etag != current.etag {
+        Some(Status::aborted(format!(
+            "book {:?} was modified, its etag is now {:?}",
+            current.id, current.etag
+        )))
+    } else {
+        None
+    }
+}
+
+#[tonic::async_trait]
+impl BookStore for BookStoreImpl {
+    async fn get_book(&self, request: Request<GetBookRequest>) -> Result<Response<Book>, Status> {
+        println!("Request from {:?}", request.remote_addr());
+
+        let (_, space) = self.space(request.metadata()).map_err(Status::not_found)?;
+        let id = request.into_inner().id;
+        let catalogue = space.catalogue.read().unwrap();
+        match catalogue.get(&id) {
+            Some(book) => Ok(Response::new(book.clone())),
+            None => Err(Status::not_found(format!("book {:?} not found", id))),
+        }
+    }
+
+    async fn list_books(
+        &self,
+        request: Request<ListBooksRequest>,
+    ) -> Result<Response<ListBooksResponse>, Status> {
+        println!("Request from {:?}", request.remote_addr());
+
+        let (_, space) = self.space(request.metadata()).map_err(Status::not_found)?;
+        let catalogue = space.catalogue.read().unwrap();
+        let response = ListBooksResponse {
+            books: catalogue.books().cloned().collect(),
+        };
+        Ok(Response::new(response))
+    }
+
+    async fn create_book(
+        &self,
+        request: Request<CreateBookRequest>,
+    ) -> Result<Response<Book>, Status> {
+        println!("Request from {:?}", request.remote_addr());
+
+        let (tenant, space) = self.space(request.metadata()).map_err(Status::not_found)?;
+        let origin = Origin::of(&request);
+        let book = request
+            .into_inner()
+            .book
+            .ok_or_else(|| Status::invalid_argument("book is required"))?;
+        let mut book = book::validate(book).map_err(Status::invalid_argument)?;
+
+        let mut catalogue = space.catalogue.write().unwrap();
+        if book.id.is_empty() {
+            book.id = self.generate_id(&catalogue);
+        } else if catalogue.contains(&book.id) {
+            return Err(Status::already_exists(format!(
+                "book {:?} already exists",
+                book.id
+            )));
+        }
+        if space.is_full(catalogue.len()) {
+            return Err(Status::resource_exhausted(format!(
+                "tenant {:?} is limited to {} books",
+                tenant, space.max_books
+            )));
+        }
+        let now: Timestamp = SystemTime::now().into();
+        book.created_at = Some(now.clone());
+        book.updated_at = Some(now);
+        catalogue.stamp(&mut book);
+
+        self.record(&tenant, &space, origin, audit::CREATE, None, Some(&book))
+            .map_err(audit_failed)?;
+        catalogue.insert(book.clone());
+        Ok(Response::new(book))
+    }
+
+    async fn update_book(
+        &self,
+        request: Request<UpdateBookRequest>,
+    ) -> Result<Response<Book>, Status> {
+        println!("Request from {:?}", request.remote_addr());
+
+        let (tenant, space) = self.space(request.metadata()).map_err(Status::not_found)?;
+        let origin = Origin::of(&request);
+        let book = request
+            .into_inner()
+            .book
+            .ok_or_else(|| Status::invalid_argument("book is required"))?;
+        let mut book = book::validate(book).map_err(Status::invalid_argument)?;
+
+        let mut catalogue = space.catalogue.write().unwrap();
+        let current = catalogue
+            .get(&book.id)
+            .ok_or_else(|| Status::not_found(format!("book {:?} not found", book.id)))?;
+        if let Some(status) = etag_mismatch(current, &book.etag) {
+            return Err(status);
+        }
+        book.created_at = current.created_at.clone();
+        book.updated_at = Some(SystemTime::now().into());
+        let current = current.clone();
+        catalogue.stamp(&mut book);
+
+        self.record(
+            &tenant,
+            &space,
+            origin,
+            audit::UPDATE,
+            Some(&current),
+            Some(&book),
+        )
+        .map_err(audit_failed)?;
+        catalogue.insert(book.clone());
+        Ok(Response::new(book))
+    }
+
+    async fn delete_book(
+        &self,
+        request: Request<DeleteBookRequest>,
+    ) -> Result<Response<()>, Status> {
+        println!("Request from {:?}", request.remote_addr());
+
+        let (tenant, space) = self.space(request.metadata()).map_err(Status::not_found)?;
+        let origin = Origin::of(&request);
+        let request = request.into_inner();
+        let mut catalogue = space.catalogue.write().unwrap();
+        let current = catalogue
+            .get(&request.id)
+            .ok_or_else(|| Status::not_found(format!("book {:?} not found", request.id)))?;
+        if let Some(status) = etag_mismatch(current, &request.etag) {
+            return Err(status);
+        }
+
+        self.record(&tenant, &space, origin, audit::DELETE, Some(current), None)
+            .map_err(audit_failed)?;
+        catalogue.remove(&request.id);
+        Ok(Response::new(()))
+    }
+
+    async fn search_books(
+        &self,
+        request: Request<SearchBooksRequest>,
+    ) -> Result<Response<SearchBooksResponse>, Status> {
+        println!("Request from {:?}", request.remote_addr());
+
+        let (_, space) = self.space(request.metadata()).map_err(Status::not_found)?;
+        let request = request.into_inner();
+        if request.limit < 0 {
+            return Err(Status::invalid_argument("limit must not be negative"));
+        }
+        let catalogue = space.catalogue.read().unwrap();
+        let results = catalogue
+            .search(&request.query, request.limit as usize)
+            .into_iter()
+            .map(|hit| SearchResult {
+                book: catalogue.get(&hit.id).cloned(),
+                score: hit.score,
+            })
+            .collect();
+        Ok(Response::new(SearchBooksResponse { results }))
+    }
+
+    async fn list_audit_events(
+        &self,
+        request: Request<ListAuditEventsRequest>,
+    ) -> Result<Response<ListAuditEventsResponse>, Status> {
+        println!("Request from {:?}", request.remote_addr());
+
+        let (tenant, space) = self.space(request.metadata()).map_err(Status::not_found)?;
+        let events = self
+            .audit
+            .lock()
+            .unwrap()
+            .query(&tenant, space.id, request.get_ref());
+        Ok(Response::new(ListAuditEventsResponse { events }))
+    }
+
+    async fn create_tenant(
+        &self,
+        request: Request<CreateTenantRequest>,
+    ) -> Result<Response<Tenant>, Status> {
+        println!("Request from {:?}", request.remote_addr());
+
+        if let Some(status) = self.unauthorized(request.metadata()) {
+            return Err(status);
+        }
+        let origin = Origin::of(&request);
+        let tenant = request
+            .into_inner()
+            .tenant
+            .ok_or_else(|| Status::invalid_argument("tenant is required"))?;
+        tenant::validate_name(&tenant.name).map_err(Status::invalid_argument)?;
+
+        let mut tenants = self.tenants.write().unwrap();
+        if tenants.get(&tenant.name).is_some() {
+            return Err(Status::already_exists(format!(
+                "tenant {:?} already exists",
+                tenant.name
+            )));
+        }
+        // the tenant is known by the sequence of the event recording its
+        // creation, which no other tenant shares, not even after a restart
+        let mut log = self.audit.lock().unwrap();
+        let id = log.next_sequence();
+        log.append(event(&tenant.name, id, origin, audit::CREATE_TENANT))
+            .map_err(audit_failed)?;
+        drop(log);
+        tenants.create(&tenant.name, id, tenant.max_books);
+        let space = tenants.get(&tenant.name).unwrap();
+        Ok(Response::new(tenant::describe(&tenant.name, &space)))
+    }
+
+    async fn delete_tenant(
+        &self,
+        request: Request<DeleteTenantRequest>,
+    ) -> Result<Response<()>, Status> {
+        println!("Request from {:?}", request.remote_addr());
+
+        if let Some(status) = self.unauthorized(request.metadata()) {
+            return Err(status);
+        }
+        let origin = Origin::of(&request);
+        let name = request.into_inner().name;
+        if name == DEFAULT_TENANT {
+            return Err(Status::failed_precondition(
+                "the default tenant cannot be deleted",
+            ));
+        }
+        let mut tenants = self.tenants.write().unwrap();
+        let space = tenants
+            .get(&name)
+            .ok_or_else(|| Status::not_found(format!("tenant {:?} not found", name)))?;
+        self.audit
+            .lock()
+            .unwrap()
+            .append(event(&name, space.id, origin, audit::DELETE_TENANT))
+            .map_err(audit_failed)?;
+        tenants.remove(&name);
+        Ok(Response::new(()))
+    }
+
+    async fn list_tenants(
+        &self,
+        request: Request<ListTenantsRequest>,
+    ) -> Result<Response<ListTenantsResponse>, Status> {
+        println!("Request from {:?}", request.remote_addr());
+
+        if let Some(status) = self.unauthorized(request.metadata()) {
+            return Err(status);
+        }
+        let tenants = self.tenants.read().unwrap().list();
+        Ok(Response::new(ListTenantsResponse { tenants }))
+    }
+}
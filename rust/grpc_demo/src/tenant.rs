@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use crate::bookstore::Tenant;
+use crate::catalogue::Catalogue;
+
+/// Request metadata selecting the tenant whose catalogue a call works on.
+pub const TENANT_METADATA: &str = "x-tenant";
+/// Request metadata carrying the token admin calls are checked against.
+pub const ADMIN_TOKEN_METADATA: &str = "x-admin-token";
+/// The tenant of requests without `x-tenant`, which cannot be deleted.
+pub const DEFAULT_TENANT: &str = "default";
+
+const MAX_NAME_LEN: usize = 63;
+
+/// A tenant's catalogue and the quota it is held to.
+#[derive(Debug, Default)]
+pub(crate) struct Space {
+    /// Unique to this incarnation of the tenant, 0 for the default tenant.
+    pub id: u64,
+    /// 0 for no limit.
+    pub max_books: u32,
+    pub catalogue: RwLock<Catalogue>,
+}
+
+impl Space {
+    pub fn new(id: u64, max_books: u32, catalogue: Catalogue) -> Self {
+        Space {
+            id,
+            max_books,
+            catalogue: RwLock::new(catalogue),
+        }
+    }
+
+    /// Whether a catalogue of `count` books is full.
+    pub fn is_full(&self, count: usize) -> bool {
+        self.max_books != 0 && count >= self.max_books as usize
+    }
+}
+
+/// Every tenant by name. Spaces are shared, so a call that has looked up its
+/// tenant finishes even if the tenant is deleted meanwhile.
+#[derive(Debug)]
+pub(crate) struct Tenants {
+    spaces: BTreeMap<String, Arc<Space>>,
+}
+
+impl Tenants {
+    /// Only the default tenant, holding `catalogue` without a quota.
+    pub fn new(catalogue: Catalogue) -> Self {
+        let mut spaces = BTreeMap::new();
+        spaces.insert(
+            DEFAULT_TENANT.to_owned(),
+            Arc::new(Space::new(0, 0, catalogue)),
+        );
+        Tenants { spaces }
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<Space>> {
+        self.spaces.get(name).cloned()
+    }
+
+    /// Adds an empty tenant with the unique `id`, `false` if the name is
+    /// taken.
+    pub fn create(&mut self, name: &str, id: u64, max_books: u32) -> bool {
+        if self.spaces.contains_key(name) {
+            return false;
+        }
+        let space = Space::new(id, max_books, Catalogue::seeded(id));
+        self.spaces.insert(name.to_owned(), Arc::new(space));
+        true
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Arc<Space>> {
+        self.spaces.remove(name)
+    }
+
+    pub fn list(&self) -> Vec<Tenant> {
+        self.spaces
+            .iter()
+            .map(|(name, space)| describe(name, space))
+            .collect()
+    }
+}
+
+pub(crate) fn describe(name: &str, space: &Space) -> Tenant {
+    Tenant {
+        name: name.to_owned(),
+        max_books: space.max_books,
+        book_count: space.catalogue.read().unwrap().len() as u32,
+        id: space.id,
+    }
+}
+
+/// Tenant names are DNS labels: lowercase letters, digits and inner dashes.
+pub(crate) fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("tenant name is required".to_owned());
+    }
+    if name.len() > MAX_NAME_LEN {
+        return Err(format!(
+            "tenant name must be at most {} characters",
+            MAX_NAME_LEN
+        ));
+    }
+    let valid = name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid || name.starts_with('-') || name.ends_with('-') {
+        return Err(format!(
+            "invalid tenant name {:?}, use lowercase letters, digits and dashes",
+            name
+        ));
+    }
+    Ok(())
+}
+
+/// Compares an admin token in constant time, so that how long a guess takes
+/// to be refused does not tell how much of it was right.
+pub(crate) fn token_matches(token: &[u8], expected: &[u8]) -> bool {
+    token.len() == expected.len()
+        && token
+            .iter()
+            .zip(expected)
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_dns_labels() {
+        assert!(validate_name("acme").is_ok());
+        assert!(validate_name("acme-2").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("Acme").is_err());
+        assert!(validate_name("acme_2").is_err());
+        assert!(validate_name("-acme").is_err());
+        assert!(validate_name(&"a".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn tokens_match_exactly() {
+        assert!(token_matches(b"secret", b"secret"));
+        assert!(!token_matches(b"secreT", b"secret"));
+        assert!(!token_matches(b"secret2", b"secret"));
+        assert!(!token_matches(b"", b"secret"));
+    }
+
+    #[test]
+    fn quotas_of_zero_are_unlimited() {
+        let unlimited = Space::new(1, 0, Catalogue::default());
+        assert!(!unlimited.is_full(1_000_000));
+
+        let limited = Space::new(1, 2, Catalogue::default());
+        assert!(!limited.is_full(1));
+        assert!(limited.is_full(2));
+    }
+
+    #[test]
+    fn tenants_are_created_once() {
+        let mut tenants = Tenants::new(Catalogue::default());
+        assert!(tenants.create("acme", 1, 10));
+        assert!(!tenants.create("acme", 2, 20));
+        assert!(!tenants.create(DEFAULT_TENANT, 3, 0));
+        let names: Vec<_> = tenants.list().into_iter().map(|t| t.name).collect();
+        assert_eq!(names, ["acme", "default"]);
+        assert!(tenants.remove("acme").is_some());
+        assert!(tenants.get("acme").is_none());
+    }
+}
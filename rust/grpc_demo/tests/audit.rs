@@ -0,0 +1,118 @@
+mod common;
+
+use std::time::{Duration, SystemTime};
+
+use grpc_demo::bookstore::{
+    Book, CreateBookRequest, DeleteBookRequest, ListAuditEventsRequest, UpdateBookRequest,
+};
+use grpc_demo::{BookStoreImpl, ServerOptions, USER_METADATA};
+use prost_types::Timestamp;
+use tonic::Request;
+
+use common::{connect, spawn_server};
+
+fn as_user<T>(user: &str, message: T) -> Request<T> {
+    let mut request = Request::new(message);
+    request
+        .metadata_mut()
+        .insert(USER_METADATA, user.parse().unwrap());
+    request
+}
+
+#[tokio::test]
+async fn writes_are_audited_with_before_and_after() {
+    let addr = spawn_server(BookStoreImpl::default(), ServerOptions::default()).await;
+    let mut client = connect(addr).await;
+    let start: Timestamp = SystemTime::now().into();
+
+    let created = client
+        .create_book(as_user(
+            "alice",
+            CreateBookRequest {
+                book: Some(Book {
+                    id: "dune".to_owned(),
+                    title: "Dune".to_owned(),
+                    authors: vec!["Frank Herbert".to_owned()],
+                    ..Book::default()
+                }),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    let updated = client
+        .update_book(as_user(
+            "bob",
+            UpdateBookRequest {
+                book: Some(Book {
+                    year: 1965,
+                    ..created.clone()
+                }),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    client
+        .delete_book(DeleteBookRequest {
+            id: "zero-to-one".to_owned(),
+            etag: "0-1".to_owned(),
+        })
+        .await
+        .unwrap();
+
+    let events = client
+        .list_audit_events(ListAuditEventsRequest {
+            book_id: "dune".to_owned(),
+            ..ListAuditEventsRequest::default()
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .events;
+    assert_eq!(events.len(), 2);
+
+    assert_eq!(events[0].action, "create");
+    // the actor is where the call came from, whoever the client claims to be
+    assert!(
+        events[0].actor.starts_with("127.0.0.1:"),
+        "{}",
+        events[0].actor
+    );
+    assert_eq!(events[0].claimed_user, "alice");
+    assert_eq!(events[0].before, None);
+    assert_eq!(events[0].after.as_ref(), Some(&created));
+
+    assert_eq!(events[1].action, "update");
+    assert_eq!(events[1].claimed_user, "bob");
+    assert_eq!(events[1].before.as_ref(), Some(&created));
+    assert_eq!(events[1].after.as_ref(), Some(&updated));
+    assert!(events[0].sequence < events[1].sequence);
+
+    let deleted = client
+        .list_audit_events(ListAuditEventsRequest {
+            book_id: "zero-to-one".to_owned(),
+            start_time: Some(start),
+            end_time: None,
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .events;
+    assert_eq!(deleted.len(), 1);
+    assert_eq!(deleted[0].action, "delete");
+    assert_eq!(deleted[0].claimed_user, "");
+    assert_eq!(deleted[0].after, None);
+
+    let future: Timestamp = (SystemTime::now() + Duration::from_secs(60)).into();
+    let none = client
+        .list_audit_events(ListAuditEventsRequest {
+            start_time: Some(future),
+            ..ListAuditEventsRequest::default()
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .events;
+    assert!(none.is_empty());
+}
@@ -0,0 +1,150 @@
+mod common;
+
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use grpc_demo::bookstore::{Book, CreateBookRequest, ListBooksRequest};
+use grpc_demo::{BalancedClient, BookStoreImpl, ClientOptions, RetryPolicy, ServerOptions};
+use tokio::net::TcpListener;
+use tonic::Code;
+
+use common::spawn_server;
+
+/// A server whose only book is named after it.
+async fn spawn_named_server(name: &str) -> SocketAddr {
+    let book = Book {
+        id: name.to_owned(),
+        title: name.to_owned(),
+        authors: vec!["test".to_owned()],
+        ..Book::default()
+    };
+    spawn_server(BookStoreImpl::new(vec![book]), ServerOptions::default()).await
+}
+
+/// An address nothing listens on.
+async fn closed_addr() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    listener.local_addr().unwrap()
+}
+
+fn uri(addr: SocketAddr) -> String {
+    format!("http://{}", addr)
+}
+
+fn fast_retries(max_attempts: u32) -> ClientOptions {
+    ClientOptions {
+        retry: RetryPolicy {
+            max_attempts,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(50),
+            multiplier: 2.0,
+        },
+        ..ClientOptions::default()
+    }
+}
+
+async fn served_by(client: &BalancedClient) -> Result<String, Code> {
+    client
+        .list_books(ListBooksRequest {})
+        .await
+        .map(|response| response.books[0].id.clone())
+        .map_err(|status| status.code())
+}
+
+#[tokio::test]
+async fn calls_rotate_round_robin_over_servers() {
+    let mut endpoints = Vec::new();
+    for name in ["a", "b", "c"] {
+        endpoints.push(uri(spawn_named_server(name).await));
+    }
+    let client = BalancedClient::new(endpoints, ClientOptions::default()).unwrap();
+
+    let mut served = Vec::new();
+    for _ in 0..6 {
+        served.push(served_by(&client).await.unwrap());
+    }
+    assert_eq!(served, ["a", "b", "c", "a", "b", "c"]);
+}
+
+#[tokio::test]
+async fn unavailable_servers_are_retried_on_the_next_one() {
+    let endpoints = vec![
+        uri(closed_addr().await),
+        uri(spawn_named_server("up").await),
+    ];
+
+    let client = BalancedClient::new(endpoints.clone(), fast_retries(1)).unwrap();
+    assert_eq!(served_by(&client).await, Err(Code::Unavailable));
+
+    let client = BalancedClient::new(endpoints, fast_retries(3)).unwrap();
+    for _ in 0..4 {
+        assert_eq!(served_by(&client).await, Ok("up".to_owned()));
+    }
+}
+
+#[tokio::test]
+async fn creates_without_an_id_are_not_retried() {
+    let endpoints = vec![
+        uri(closed_addr().await),
+        uri(spawn_named_server("up").await),
+    ];
+    let client = BalancedClient::new(endpoints, fast_retries(3)).unwrap();
+    let create = |id: &str| CreateBookRequest {
+        book: Some(Book {
+            id: id.to_owned(),
+            title: "Dune".to_owned(),
+            authors: vec!["Frank Herbert".to_owned()],
+            ..Book::default()
+        }),
+    };
+
+    // the server may have created the book before the failure
+    let status = client.create_book(create("")).await.unwrap_err();
+    assert_eq!(status.code(), Code::Unavailable);
+    // a second attempt with the same id would fail with `ALREADY_EXISTS`
+    assert_eq!(client.create_book(create("dune")).await.unwrap().id, "dune");
+}
+
+#[tokio::test]
+async fn clients_need_endpoints_and_a_growing_backoff() {
+    let endpoints: Vec<String> = Vec::new();
+    assert!(BalancedClient::new(endpoints, ClientOptions::default()).is_err());
+
+    let mut options = fast_retries(3);
+    options.retry.multiplier = 0.0;
+    let endpoints = vec![uri(closed_addr().await)];
+    assert!(BalancedClient::new(endpoints, options).is_err());
+}
+
+#[tokio::test]
+async fn retries_give_up_after_max_attempts() {
+    let endpoints = vec![uri(closed_addr().await), uri(closed_addr().await)];
+    let client = BalancedClient::new(endpoints, fast_retries(3)).unwrap();
+
+    let start = Instant::now();
+    assert_eq!(served_by(&client).await, Err(Code::Unavailable));
+    // backed off 10ms then 20ms between the three attempts
+    assert!(start.elapsed() >= Duration::from_millis(30));
+}
+
+#[tokio::test]
+async fn calls_fail_when_the_deadline_passes() {
+    // accepts connections but never answers
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let mut connections = Vec::new();
+        loop {
+            connections.push(listener.accept().await.unwrap());
+        }
+    });
+
+    let client = BalancedClient::new(vec![uri(addr)], ClientOptions::default()).unwrap();
+    let start = Instant::now();
+    let result = client
+        .with_timeout(Duration::from_millis(100))
+        .list_books(ListBooksRequest {})
+        .await;
+    assert_eq!(result.unwrap_err().code(), Code::DeadlineExceeded);
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
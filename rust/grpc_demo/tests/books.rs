@@ -0,0 +1,152 @@
+mod common;
+
+use grpc_demo::bookstore::{
+    Book, CreateBookRequest, DeleteBookRequest, GetBookRequest, SearchBooksRequest,
+    SearchBooksResponse, UpdateBookRequest,
+};
+use grpc_demo::{BookStoreImpl, ServerOptions};
+use tonic::Code;
+
+use common::{connect, spawn_server};
+
+fn rust_book() -> Book {
+    Book {
+        title: "The Rust Programming Language".to_owned(),
+        authors: vec!["Steve Klabnik".to_owned(), "Carol Nichols".to_owned()],
+        isbn: "978-1-59327-828-1".to_owned(),
+        tags: vec!["Rust".to_owned()],
+        publisher: "No Starch Press".to_owned(),
+        year: 2018,
+        ..Book::default()
+    }
+}
+
+#[tokio::test]
+async fn create_validates_and_stamps_books() {
+    let addr = spawn_server(BookStoreImpl::default(), ServerOptions::default()).await;
+    let mut client = connect(addr).await;
+
+    let created = client
+        .create_book(CreateBookRequest {
+            book: Some(rust_book()),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(!created.id.is_empty());
+    assert_eq!(created.isbn, "9781593278281");
+    assert_eq!(created.tags, ["rust"]);
+    assert!(created.created_at.is_some());
+    assert_eq!(created.created_at, created.updated_at);
+
+    let fetched = client
+        .get_book(GetBookRequest {
+            id: created.id.clone(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(fetched, created);
+
+    let status = client
+        .create_book(CreateBookRequest {
+            book: Some(Book {
+                isbn: "978-1-59327-828-0".to_owned(),
+                ..rust_book()
+            }),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert!(status.message().contains("ISBN"), "{}", status.message());
+}
+
+#[tokio::test]
+async fn update_keeps_creation_time() {
+    let addr = spawn_server(BookStoreImpl::default(), ServerOptions::default()).await;
+    let mut client = connect(addr).await;
+
+    let created = client
+        .create_book(CreateBookRequest {
+            book: Some(rust_book()),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    let updated = client
+        .update_book(UpdateBookRequest {
+            book: Some(Book {
+                tags: vec!["rust".to_owned(), "programming".to_owned()],
+                created_at: None,
+                ..created.clone()
+            }),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(updated.tags, ["rust", "programming"]);
+    assert_eq!(updated.created_at, created.created_at);
+    let seconds = |book: &Book| book.updated_at.as_ref().unwrap().seconds;
+    assert!(seconds(&updated) >= seconds(&created));
+}
+
+#[tokio::test]
+async fn search_index_follows_catalogue_writes() {
+    let addr = spawn_server(BookStoreImpl::default(), ServerOptions::default()).await;
+    let mut client = connect(addr).await;
+    let search = |query: &str| SearchBooksRequest {
+        query: query.to_owned(),
+        limit: 0,
+    };
+
+    let created = client
+        .create_book(CreateBookRequest {
+            book: Some(rust_book()),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    let results = client
+        .search_books(search("RUST prog"))
+        .await
+        .unwrap()
+        .into_inner()
+        .results;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].book.as_ref().unwrap().id, created.id);
+
+    let updated = client
+        .update_book(UpdateBookRequest {
+            book: Some(Book {
+                title: "Programming Rust".to_owned(),
+                authors: vec!["Jim Blandy".to_owned()],
+                ..created
+            }),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    let search_results =
+        |response: tonic::Response<SearchBooksResponse>| response.into_inner().results.len();
+    assert_eq!(
+        search_results(client.search_books(search("klabnik")).await.unwrap()),
+        0
+    );
+    assert_eq!(
+        search_results(client.search_books(search("blandy")).await.unwrap()),
+        1
+    );
+
+    client
+        .delete_book(DeleteBookRequest {
+            id: updated.id,
+            etag: updated.etag,
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        search_results(client.search_books(search("blandy")).await.unwrap()),
+        0
+    );
+}
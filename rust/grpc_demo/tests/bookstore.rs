@@ -0,0 +1,252 @@
+mod common;
+
+use grpc_demo::bookstore::book_store_client::BookStoreClient;
+use grpc_demo::bookstore::{
+    Book, CreateBookRequest, DeleteBookRequest, GetBookRequest, ListBooksRequest,
+    SearchBooksRequest, UpdateBookRequest,
+};
+use tonic::transport::Channel;
+use tonic::Code;
+
+use common::with_each_transport;
+
+fn dune() -> Book {
+    Book {
+        id: "dune".to_owned(),
+        title: "Dune".to_owned(),
+        authors: vec!["Frank Herbert".to_owned()],
+        isbn: "0-441-17271-7".to_owned(),
+        year: 1965,
+        ..Book::default()
+    }
+}
+
+async fn zero_to_one(client: &mut BookStoreClient<Channel>) -> Book {
+    client
+        .get_book(GetBookRequest {
+            id: "zero-to-one".to_owned(),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+}
+
+#[tokio::test]
+async fn get_book() {
+    with_each_transport(|mut client| async move {
+        let book = client
+            .get_book(GetBookRequest {
+                id: "zero-to-one".to_owned(),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(book.title, "Zero to One");
+
+        let status = client
+            .get_book(GetBookRequest {
+                id: "missing".to_owned(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn list_books() {
+    with_each_transport(|mut client| async move {
+        let books = client
+            .list_books(ListBooksRequest {})
+            .await
+            .unwrap()
+            .into_inner()
+            .books;
+        let ids: Vec<_> = books.iter().map(|book| book.id.as_str()).collect();
+        assert_eq!(ids, ["zero-to-one"]);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn create_book() {
+    with_each_transport(|mut client| async move {
+        let created = client
+            .create_book(CreateBookRequest { book: Some(dune()) })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(created.id, "dune");
+        assert_eq!(created.isbn, "0441172717");
+
+        let status = client
+            .create_book(CreateBookRequest { book: Some(dune()) })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::AlreadyExists);
+
+        let status = client
+            .create_book(CreateBookRequest { book: None })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        let status = client
+            .create_book(CreateBookRequest {
+                book: Some(Book {
+                    id: String::new(),
+                    authors: vec![],
+                    ..dune()
+                }),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn update_book() {
+    with_each_transport(|mut client| async move {
+        let book = zero_to_one(&mut client).await;
+        let updated = client
+            .update_book(UpdateBookRequest {
+                book: Some(Book {
+                    publisher: "Crown".to_owned(),
+                    ..book.clone()
+                }),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(updated.publisher, "Crown");
+        assert!(updated.updated_at.is_some());
+
+        let status = client
+            .update_book(UpdateBookRequest { book: Some(dune()) })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
+
+        let status = client
+            .update_book(UpdateBookRequest {
+                book: Some(Book {
+                    isbn: "123".to_owned(),
+                    ..book
+                }),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn delete_book() {
+    with_each_transport(|mut client| async move {
+        let etag = zero_to_one(&mut client).await.etag;
+        let delete = |etag: &str| DeleteBookRequest {
+            id: "zero-to-one".to_owned(),
+            etag: etag.to_owned(),
+        };
+
+        let status = client.delete_book(delete("")).await.unwrap_err();
+        assert_eq!(status.code(), Code::FailedPrecondition);
+
+        client.delete_book(delete(&etag)).await.unwrap();
+
+        let status = client.delete_book(delete(&etag)).await.unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
+
+        let books = client
+            .list_books(ListBooksRequest {})
+            .await
+            .unwrap()
+            .into_inner()
+            .books;
+        assert!(books.is_empty());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn concurrent_writers_need_the_current_etag() {
+    with_each_transport(|mut client| async move {
+        let first = zero_to_one(&mut client).await;
+        let second = first.clone();
+
+        let updated = client
+            .update_book(UpdateBookRequest {
+                book: Some(Book {
+                    year: 2015,
+                    ..first
+                }),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_ne!(updated.etag, second.etag);
+
+        let status = client
+            .update_book(UpdateBookRequest {
+                book: Some(Book {
+                    publisher: "Crown".to_owned(),
+                    ..second.clone()
+                }),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::Aborted);
+
+        let status = client
+            .delete_book(DeleteBookRequest {
+                id: second.id,
+                etag: second.etag,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::Aborted);
+
+        let status = client
+            .update_book(UpdateBookRequest {
+                book: Some(Book {
+                    etag: String::new(),
+                    ..updated
+                }),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::FailedPrecondition);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn search_books() {
+    with_each_transport(|mut client| async move {
+        let results = client
+            .search_books(SearchBooksRequest {
+                query: "thiel".to_owned(),
+                limit: 10,
+            })
+            .await
+            .unwrap()
+            .into_inner()
+            .results;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].book.as_ref().unwrap().id, "zero-to-one");
+
+        let status = client
+            .search_books(SearchBooksRequest {
+                query: "thiel".to_owned(),
+                limit: -1,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    })
+    .await;
+}
@@ -0,0 +1,76 @@
+// Each test crate uses a different subset of the harness.
+#![allow(dead_code)]
+
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use grpc_demo::bookstore::book_store_client::BookStoreClient;
+use grpc_demo::{gateway, BookStoreImpl, ClientOptions, ServerOptions};
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::{Channel, Endpoint, Server, Uri};
+
+/// Serves `bookstore` on an ephemeral localhost port for the rest of the test.
+pub async fn spawn_server(bookstore: BookStoreImpl, options: ServerOptions) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let service = gateway::grpc_web().enable(Arc::new(bookstore).into_service(&options).unwrap());
+    tokio::spawn(async move {
+        Server::builder()
+            .accept_http1(true)
+            .add_service(service)
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .await
+            .unwrap();
+    });
+    addr
+}
+
+pub async fn connect(addr: SocketAddr) -> BookStoreClient<Channel> {
+    grpc_demo::connect(format!("http://{}", addr), &ClientOptions::default())
+        .await
+        .unwrap()
+}
+
+/// Serves `bookstore` over an in-memory duplex pipe instead of a socket.
+/// Requests carry no peer address, so per-peer rate limits do not apply.
+pub async fn connect_in_memory(
+    bookstore: BookStoreImpl,
+    options: ServerOptions,
+) -> BookStoreClient<Channel> {
+    let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+    let service = Arc::new(bookstore).into_service(&options).unwrap();
+    tokio::spawn(async move {
+        Server::builder()
+            .add_service(service)
+            .serve_with_incoming(tokio_stream::once(Ok::<_, io::Error>(server_io)))
+            .await
+            .unwrap();
+    });
+
+    // the channel only ever dials once, later calls reuse the connection
+    let client_io = Arc::new(Mutex::new(Some(client_io)));
+    let channel = Endpoint::from_static("http://in-memory")
+        .connect_with_connector(tower::service_fn(move |_: Uri| {
+            let client_io = client_io.lock().unwrap().take();
+            async move { client_io.ok_or_else(|| io::Error::other("already connected")) }
+        }))
+        .await
+        .unwrap();
+    BookStoreClient::new(channel)
+}
+
+/// Runs `test` once against a TCP server and once against an in-memory one,
+/// each serving a fresh `BookStoreImpl::default()`.
+pub async fn with_each_transport<F, Fut>(test: F)
+where
+    F: Fn(BookStoreClient<Channel>) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let addr = spawn_server(BookStoreImpl::default(), ServerOptions::default()).await;
+    test(connect(addr).await).await;
+
+    test(connect_in_memory(BookStoreImpl::default(), ServerOptions::default()).await).await;
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use grpc_demo::bookstore::book_store_client::BookStoreClient;
+use grpc_demo::bookstore::{Book, ListBooksRequest};
+use grpc_demo::{BookStoreImpl, ClientOptions, Compression, ServerOptions};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
+use tonic::transport::{Channel, Endpoint, Uri};
+use tonic::Code;
+
+use common::spawn_server;
+
+/// A TCP stream that counts how many bytes were read from the server.
+struct CountingStream {
+    inner: TcpStream,
+    read: Arc<AtomicUsize>,
+}
+
+impl AsyncRead for CountingStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let before = buf.filled().len();
+        let poll = Pin::new(&mut self.inner).poll_read(cx, buf);
+        self.read
+            .fetch_add(buf.filled().len() - before, Ordering::SeqCst);
+        poll
+    }
+}
+
+impl AsyncWrite for CountingStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+fn catalogue(size: usize) -> Vec<Book> {
+    (0..size)
+        .map(|i| Book {
+            id: format!("book-{}", i),
+            title: "The Rust Programming Language".to_owned(),
+            authors: vec!["Steve Klabnik".to_owned(), "Carol Nichols".to_owned()],
+            year: 2018,
+            ..Book::default()
+        })
+        .collect()
+}
+
+async fn counting_client(addr: SocketAddr, read: Arc<AtomicUsize>) -> BookStoreClient<Channel> {
+    let channel = Endpoint::from_shared(format!("http://{}", addr))
+        .unwrap()
+        .connect_with_connector(tower::service_fn(move |_: Uri| {
+            let read = read.clone();
+            async move {
+                let inner = TcpStream::connect(addr).await?;
+                Ok::<_, io::Error>(CountingStream { inner, read })
+            }
+        }))
+        .await
+        .unwrap();
+    BookStoreClient::new(channel)
+}
+
+async fn list_books_bytes_read(
+    addr: SocketAddr,
+    options: &ClientOptions,
+) -> (usize, Option<String>) {
+    let read = Arc::new(AtomicUsize::new(0));
+    let mut client = options.apply(counting_client(addr, read.clone()).await);
+    let before = read.load(Ordering::SeqCst);
+
+    let response = client.list_books(ListBooksRequest {}).await.unwrap();
+    let encoding = response
+        .metadata()
+        .get("grpc-encoding")
+        .map(|value| value.to_str().unwrap().to_owned());
+    assert_eq!(response.into_inner().books.len(), 1000);
+
+    (read.load(Ordering::SeqCst) - before, encoding)
+}
+
+#[tokio::test]
+async fn large_list_books_response_is_gzip_compressed() {
+    let addr = spawn_server(
+        BookStoreImpl::new(catalogue(1000)),
+        ServerOptions::default(),
+    )
+    .await;
+
+    let plain = ClientOptions::default();
+    let (plain_bytes, plain_encoding) = list_books_bytes_read(addr, &plain).await;
+    assert_eq!(plain_encoding, None);
+
+    let gzip = ClientOptions {
+        compression: Compression::GZIP,
+        ..ClientOptions::default()
+    };
+    let (gzip_bytes, gzip_encoding) = list_books_bytes_read(addr, &gzip).await;
+    assert_eq!(gzip_encoding.as_deref(), Some("gzip"));
+
+    assert!(
+        gzip_bytes * 10 < plain_bytes,
+        "gzip response used {} bytes, uncompressed {} bytes",
+        gzip_bytes,
+        plain_bytes
+    );
+}
+
+#[tokio::test]
+async fn server_without_gzip_answers_uncompressed() {
+    let options = ServerOptions {
+        compression: Compression::NONE,
+        ..ServerOptions::default()
+    };
+    let addr = spawn_server(BookStoreImpl::new(catalogue(1000)), options).await;
+
+    let options = ClientOptions {
+        compression: Compression {
+            send_gzip: false,
+            accept_gzip: true,
+        },
+        ..ClientOptions::default()
+    };
+    let (_, encoding) = list_books_bytes_read(addr, &options).await;
+    assert_eq!(encoding, None);
+}
+
+#[tokio::test]
+async fn server_without_gzip_rejects_compressed_requests() {
+    let options = ServerOptions {
+        compression: Compression::NONE,
+        ..ServerOptions::default()
+    };
+    let addr = spawn_server(BookStoreImpl::new(catalogue(1000)), options).await;
+
+    let options = ClientOptions {
+        compression: Compression::GZIP,
+        ..ClientOptions::default()
+    };
+    let mut client = grpc_demo::connect(format!("http://{}", addr), &options)
+        .await
+        .unwrap();
+    let status = client.list_books(ListBooksRequest {}).await.unwrap_err();
+    assert_eq!(status.code(), Code::Unimplemented);
+}
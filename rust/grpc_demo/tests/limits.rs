@@ -0,0 +1,30 @@
+mod common;
+
+use grpc_demo::bookstore::ListBooksRequest;
+use grpc_demo::{BookStoreImpl, Limits, RateLimit, ServerOptions, RETRY_AFTER};
+use tonic::Code;
+
+use common::{connect, spawn_server};
+
+#[tokio::test]
+async fn peer_over_rate_limit_gets_resource_exhausted() {
+    let options = ServerOptions {
+        limits: Limits {
+            rate: Some(RateLimit {
+                burst: 2,
+                per_second: 0.5,
+            }),
+            max_concurrent_requests: None,
+        },
+        ..ServerOptions::default()
+    };
+    let addr = spawn_server(BookStoreImpl::default(), options).await;
+    let mut client = connect(addr).await;
+
+    for _ in 0..2 {
+        client.list_books(ListBooksRequest {}).await.unwrap();
+    }
+    let status = client.list_books(ListBooksRequest {}).await.unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+    assert_eq!(status.metadata().get(RETRY_AFTER).unwrap(), "2");
+}
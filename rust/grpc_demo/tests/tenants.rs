@@ -0,0 +1,218 @@
+mod common;
+
+use grpc_demo::bookstore::{
+    Book, CreateBookRequest, CreateTenantRequest, DeleteTenantRequest, GetBookRequest,
+    ListAuditEventsRequest, ListBooksRequest, ListTenantsRequest, Tenant,
+};
+use grpc_demo::{BalancedClient, BookStoreImpl, ClientOptions, ServerOptions, DEFAULT_TENANT};
+use tonic::Code;
+
+use common::spawn_server;
+
+/// A client of a fresh server, allowed to make admin calls.
+async fn admin() -> BalancedClient {
+    let bookstore = BookStoreImpl::default().with_admin_token("secret");
+    let addr = spawn_server(bookstore, ServerOptions::default()).await;
+    let options = ClientOptions {
+        admin_token: Some("secret".to_owned()),
+        ..ClientOptions::default()
+    };
+    BalancedClient::new(vec![format!("http://{}", addr)], options).unwrap()
+}
+
+fn tenant(name: &str, max_books: u32) -> CreateTenantRequest {
+    CreateTenantRequest {
+        tenant: Some(Tenant {
+            name: name.to_owned(),
+            max_books,
+            ..Tenant::default()
+        }),
+    }
+}
+
+fn book(id: &str) -> CreateBookRequest {
+    CreateBookRequest {
+        book: Some(Book {
+            id: id.to_owned(),
+            title: id.to_owned(),
+            authors: vec!["Anonymous".to_owned()],
+            ..Book::default()
+        }),
+    }
+}
+
+async fn book_ids(client: &BalancedClient) -> Vec<String> {
+    let response = client.list_books(ListBooksRequest {}).await.unwrap();
+    response.books.into_iter().map(|book| book.id).collect()
+}
+
+#[tokio::test]
+async fn tenants_have_isolated_catalogues() {
+    let admin = admin().await;
+    let acme_id = admin.create_tenant(tenant("acme", 0)).await.unwrap().id;
+    let globex_id = admin.create_tenant(tenant("globex", 0)).await.unwrap().id;
+    assert_ne!(acme_id, globex_id);
+    let acme = admin.with_tenant("acme");
+    let globex = admin.with_tenant("globex");
+
+    acme.create_book(book("dune")).await.unwrap();
+    // the same id is free in every tenant
+    let created = globex.create_book(book("dune")).await.unwrap();
+    assert_eq!(created.etag, format!("{}-1", globex_id));
+    globex.create_book(book("emma")).await.unwrap();
+
+    assert_eq!(book_ids(&admin).await, ["zero-to-one"]);
+    assert_eq!(book_ids(&acme).await, ["dune"]);
+    assert_eq!(book_ids(&globex).await, ["dune", "emma"]);
+    let status = acme
+        .get_book(GetBookRequest {
+            id: "emma".to_owned(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+
+    let events = acme
+        .list_audit_events(ListAuditEventsRequest::default())
+        .await
+        .unwrap()
+        .events;
+    let actions: Vec<_> = events.iter().map(|event| event.action.as_str()).collect();
+    assert_eq!(actions, ["create_tenant", "create"]);
+    assert!(events
+        .iter()
+        .all(|event| event.tenant == "acme" && event.tenant_id == acme_id));
+
+    let tenants = admin
+        .list_tenants(ListTenantsRequest {})
+        .await
+        .unwrap()
+        .tenants;
+    let counts: Vec<_> = tenants
+        .iter()
+        .map(|tenant| (tenant.name.as_str(), tenant.book_count))
+        .collect();
+    assert_eq!(counts, [("acme", 1), (DEFAULT_TENANT, 1), ("globex", 2)]);
+}
+
+#[tokio::test]
+async fn quotas_limit_the_books_of_a_tenant() {
+    let admin = admin().await;
+    admin.create_tenant(tenant("acme", 2)).await.unwrap();
+    let acme = admin.with_tenant("acme");
+
+    acme.create_book(book("dune")).await.unwrap();
+    acme.create_book(book("emma")).await.unwrap();
+    let status = acme.create_book(book("ulysses")).await.unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+    assert_eq!(book_ids(&acme).await, ["dune", "emma"]);
+}
+
+#[tokio::test]
+async fn deleted_tenants_are_gone() {
+    let admin = admin().await;
+    admin.create_tenant(tenant("acme", 0)).await.unwrap();
+    let acme = admin.with_tenant("acme");
+    let old = acme.create_book(book("dune")).await.unwrap();
+
+    let status = admin.create_tenant(tenant("acme", 0)).await.unwrap_err();
+    assert_eq!(status.code(), Code::AlreadyExists);
+    admin
+        .delete_tenant(DeleteTenantRequest {
+            name: "acme".to_owned(),
+        })
+        .await
+        .unwrap();
+
+    let status = acme.list_books(ListBooksRequest {}).await.unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+    let status = admin
+        .delete_tenant(DeleteTenantRequest {
+            name: "acme".to_owned(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+    let status = admin
+        .delete_tenant(DeleteTenantRequest {
+            name: DEFAULT_TENANT.to_owned(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+
+    // a new tenant with the same name starts out empty, with etags and an
+    // audit log of its own
+    admin.create_tenant(tenant("acme", 0)).await.unwrap();
+    assert!(book_ids(&acme).await.is_empty());
+    let new = acme.create_book(book("dune")).await.unwrap();
+    assert_ne!(new.etag, old.etag);
+    let events = acme
+        .list_audit_events(ListAuditEventsRequest::default())
+        .await
+        .unwrap()
+        .events;
+    let actions: Vec<_> = events.iter().map(|event| event.action.as_str()).collect();
+    assert_eq!(actions, ["create_tenant", "create"]);
+}
+
+#[tokio::test]
+async fn invalid_tenant_names_are_rejected() {
+    let admin = admin().await;
+    let status = admin
+        .create_tenant(tenant("Acme Corp", 0))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn admin_calls_require_the_admin_token() {
+    let bookstore = BookStoreImpl::default().with_admin_token("secret");
+    let addr = spawn_server(bookstore, ServerOptions::default()).await;
+    let client = |admin_token: Option<&str>| {
+        let options = ClientOptions {
+            admin_token: admin_token.map(str::to_owned),
+            ..ClientOptions::default()
+        };
+        BalancedClient::new(vec![format!("http://{}", addr)], options).unwrap()
+    };
+
+    let anonymous = client(None);
+    let status = anonymous
+        .create_tenant(tenant("acme", 0))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+    let status = client(Some("guess"))
+        .list_tenants(ListTenantsRequest {})
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::PermissionDenied);
+
+    client(Some("secret"))
+        .create_tenant(tenant("acme", 0))
+        .await
+        .unwrap();
+    // book calls do not need the token
+    anonymous
+        .with_tenant("acme")
+        .create_book(book("dune"))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn admin_calls_are_refused_without_a_server_token() {
+    let addr = spawn_server(BookStoreImpl::default(), ServerOptions::default()).await;
+    let options = ClientOptions {
+        admin_token: Some(String::new()),
+        ..ClientOptions::default()
+    };
+    let client = BalancedClient::new(vec![format!("http://{}", addr)], options).unwrap();
+    let status = client
+        .list_tenants(ListTenantsRequest {})
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::PermissionDenied);
+}
@@ -0,0 +1,246 @@
+mod common;
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract::ConnectInfo;
+use grpc_demo::bookstore::{Book, GetBookRequest};
+use grpc_demo::{gateway, BookStoreImpl, Limiter, Limits, RateLimit, ServerOptions};
+use hyper::body::{to_bytes, Bytes};
+use hyper::{Body, Client, Method, Request, StatusCode};
+use prost::Message;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use common::spawn_server;
+
+/// Frames a message the way gRPC-Web does: flag byte, big endian length, payload.
+fn grpc_web_frame(message: &impl Message) -> Bytes {
+    let payload = message.encode_to_vec();
+    let mut frame = vec![0u8];
+    frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    frame.extend_from_slice(&payload);
+    frame.into()
+}
+
+#[tokio::test]
+async fn grpc_web_get_book_over_http1() {
+    let addr = spawn_server(BookStoreImpl::default(), ServerOptions::default()).await;
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("http://{}/bookstore.BookStore/GetBook", addr))
+        .header("content-type", "application/grpc-web+proto")
+        .header("x-grpc-web", "1")
+        .body(Body::from(grpc_web_frame(&GetBookRequest {
+            id: "zero-to-one".to_owned(),
+        })))
+        .unwrap();
+    let response = Client::new().request(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/grpc-web+proto"
+    );
+
+    let body = to_bytes(response.into_body()).await.unwrap();
+    assert_eq!(body[0], 0, "first frame should be a data frame");
+    let len = u32::from_be_bytes(body[1..5].try_into().unwrap()) as usize;
+    let book = Book::decode(&body[5..5 + len]).unwrap();
+    assert_eq!(book.id, "zero-to-one");
+    assert_eq!(book.title, "Zero to One");
+
+    let trailers = String::from_utf8_lossy(&body[5 + len + 5..]);
+    assert_eq!(body[5 + len], 0x80, "second frame should hold the trailers");
+    assert!(trailers.contains("grpc-status:0"), "{}", trailers);
+}
+
+async fn get_json(uri: &str) -> (StatusCode, Value) {
+    let router = gateway::router(
+        Arc::new(BookStoreImpl::default()),
+        Limiter::default(),
+        Vec::new(),
+    );
+    let response = router
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn rest_get_book_returns_json() {
+    let (status, body) = get_json("/v1/books/zero-to-one").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body,
+        json!({
+            "id": "zero-to-one",
+            "title": "Zero to One",
+            "authors": ["Peter Thiel", "Blake Masters"],
+            "isbn": "9780804139298",
+            "tags": ["business", "startups"],
+            "publisher": "Crown Business",
+            "year": 2014,
+            "createdAt": null,
+            "updatedAt": null,
+            "etag": "0-1",
+        })
+    );
+}
+
+#[tokio::test]
+async fn rest_list_books_returns_json() {
+    let (status, body) = get_json("/v1/books").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["books"][0]["id"], "zero-to-one");
+}
+
+#[tokio::test]
+async fn rest_search_books_returns_ranked_results() {
+    let (status, body) = get_json("/v1/search?query=zero&limit=5").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["results"][0]["book"]["id"], "zero-to-one");
+    assert_eq!(body["results"][0]["score"], 2.0);
+}
+
+#[tokio::test]
+async fn rest_writes_use_if_match() {
+    let router = gateway::router(
+        Arc::new(BookStoreImpl::default()),
+        Limiter::default(),
+        Vec::new(),
+    );
+    let send = |method: Method, if_match: &str, body: Value| {
+        let request = Request::builder()
+            .method(method)
+            .uri("/v1/books/zero-to-one")
+            .header("content-type", "application/json")
+            .header("if-match", if_match)
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        router.clone().oneshot(request)
+    };
+    let book = json!({ "title": "Zero to One", "authors": ["Peter Thiel"] });
+
+    let response = send(Method::PUT, "\"0-1\"", book.clone()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["etag"], "\"0-2\"");
+
+    let response = send(Method::PUT, "\"0-1\"", book.clone()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+
+    let response = send(Method::PUT, "W/\"0-2\"", book.clone()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["etag"], "\"0-3\"");
+
+    let response = send(Method::PUT, "*", book).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["etag"], "\"0-4\"");
+
+    let response = send(Method::DELETE, "*", Value::Null).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn rest_writes_without_an_etag_are_refused() {
+    let router = gateway::router(
+        Arc::new(BookStoreImpl::default()),
+        Limiter::default(),
+        Vec::new(),
+    );
+    let request = Request::delete("/v1/books/zero-to-one")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PRECONDITION_REQUIRED);
+}
+
+#[tokio::test]
+async fn rest_gateway_is_rate_limited_per_peer() {
+    let limits = Limits {
+        rate: Some(RateLimit {
+            burst: 1,
+            per_second: 0.5,
+        }),
+        max_concurrent_requests: None,
+    };
+    let router = gateway::router(
+        Arc::new(BookStoreImpl::default()),
+        Limiter::new(&limits).unwrap(),
+        Vec::new(),
+    );
+    let get = |peer: &str| {
+        let addr: SocketAddr = peer.parse().unwrap();
+        let request = Request::get("/v1/books")
+            .extension(ConnectInfo(addr))
+            .body(Body::empty())
+            .unwrap();
+        router.clone().oneshot(request)
+    };
+
+    assert_eq!(get("10.0.0.1:1000").await.unwrap().status(), StatusCode::OK);
+    let response = get("10.0.0.1:2000").await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()["retry-after"], "2");
+    assert_eq!(get("10.0.0.2:1000").await.unwrap().status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn rest_writes_are_audited_with_the_peer_address() {
+    let router = gateway::router(
+        Arc::new(BookStoreImpl::default()),
+        Limiter::default(),
+        Vec::new(),
+    );
+    let addr: SocketAddr = "10.0.0.1:1234".parse().unwrap();
+    let request = Request::delete("/v1/books/zero-to-one")
+        .header("if-match", "\"0-1\"")
+        .header("x-user", "mallory")
+        .extension(ConnectInfo(addr))
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let request = Request::get("/v1/audit-events")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    let body: Value =
+        serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap();
+    assert_eq!(body["events"][0]["actor"], "10.0.0.1:1234");
+    assert_eq!(body["events"][0]["claimedUser"], "mallory");
+}
+
+#[tokio::test]
+async fn only_allowed_origins_pass_cors() {
+    let router = gateway::router(
+        Arc::new(BookStoreImpl::default()),
+        Limiter::default(),
+        vec!["https://books.example".parse().unwrap()],
+    );
+    let preflight = |origin: &str| {
+        let request = Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/v1/tenants")
+            .header("origin", origin)
+            .header("access-control-request-method", "POST")
+            .header("access-control-request-headers", "x-admin-token")
+            .body(Body::empty())
+            .unwrap();
+        router.clone().oneshot(request)
+    };
+
+    let response = preflight("https://books.example").await.unwrap();
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        "https://books.example"
+    );
+    let response = preflight("https://evil.example").await.unwrap();
+    assert!(!response
+        .headers()
+        .contains_key("access-control-allow-origin"));
+}